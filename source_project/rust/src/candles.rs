@@ -0,0 +1,341 @@
+use crate::{number, CandleData};
+use chrono::{DateTime, Datelike, Months, NaiveTime, Utc};
+
+// candleSnapshot returns at most this many candles per request
+const MAX_CANDLES_PER_REQUEST: u64 = 5000;
+const MINUTE_MS: u64 = 60 * 1000;
+const HOUR_MS: u64 = 60 * MINUTE_MS;
+const DAY_MS: u64 = 24 * HOUR_MS;
+const WEEK_MS: u64 = 7 * DAY_MS;
+
+// Weekly candles open on Mondays, three days before each whole week since
+// the epoch (a Thursday)
+const WEEK_OFFSET_MS: u64 = 3 * DAY_MS;
+
+/// Candle intervals the API serves.
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CandleInterval {
+    OneMinute,
+    ThreeMinutes,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    OneHour,
+    TwoHours,
+    FourHours,
+    EightHours,
+    TwelveHours,
+    OneDay,
+    ThreeDays,
+    OneWeek,
+    OneMonth,
+}
+
+impl CandleInterval {
+    pub const ALL: [CandleInterval; 14] = [
+        Self::OneMinute,
+        Self::ThreeMinutes,
+        Self::FiveMinutes,
+        Self::FifteenMinutes,
+        Self::ThirtyMinutes,
+        Self::OneHour,
+        Self::TwoHours,
+        Self::FourHours,
+        Self::EightHours,
+        Self::TwelveHours,
+        Self::OneDay,
+        Self::ThreeDays,
+        Self::OneWeek,
+        Self::OneMonth,
+    ];
+
+    /// Name used on the wire, e.g. "1h".
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OneMinute => "1m",
+            Self::ThreeMinutes => "3m",
+            Self::FiveMinutes => "5m",
+            Self::FifteenMinutes => "15m",
+            Self::ThirtyMinutes => "30m",
+            Self::OneHour => "1h",
+            Self::TwoHours => "2h",
+            Self::FourHours => "4h",
+            Self::EightHours => "8h",
+            Self::TwelveHours => "12h",
+            Self::OneDay => "1d",
+            Self::ThreeDays => "3d",
+            Self::OneWeek => "1w",
+            Self::OneMonth => "1M",
+        }
+    }
+
+    // Case matters: "1m" is a minute, "1M" a month
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|interval| interval.as_str() == name)
+    }
+
+    pub(crate) fn step(self) -> CandleStep {
+        let millis = match self {
+            Self::OneMinute => MINUTE_MS,
+            Self::ThreeMinutes => 3 * MINUTE_MS,
+            Self::FiveMinutes => 5 * MINUTE_MS,
+            Self::FifteenMinutes => 15 * MINUTE_MS,
+            Self::ThirtyMinutes => 30 * MINUTE_MS,
+            Self::OneHour => HOUR_MS,
+            Self::TwoHours => 2 * HOUR_MS,
+            Self::FourHours => 4 * HOUR_MS,
+            Self::EightHours => 8 * HOUR_MS,
+            Self::TwelveHours => 12 * HOUR_MS,
+            Self::OneDay => DAY_MS,
+            Self::ThreeDays => 3 * DAY_MS,
+            Self::OneWeek => return CandleStep::Week,
+            Self::OneMonth => return CandleStep::Month,
+        };
+        CandleStep::Fixed(millis)
+    }
+}
+
+/// Wire name of an interval, e.g. "1h".
+#[uniffi::export]
+pub fn candle_interval_name(interval: CandleInterval) -> String {
+    interval.as_str().to_string()
+}
+
+/// Interval for a wire name such as "15m"; None for names the API does not serve.
+#[uniffi::export]
+pub fn parse_candle_interval(name: String) -> Option<CandleInterval> {
+    CandleInterval::from_name(&name)
+}
+
+/// Length of an interval in milliseconds; months count as 30 days.
+#[uniffi::export]
+pub fn candle_interval_millis(interval: CandleInterval) -> u64 {
+    match interval.step() {
+        CandleStep::Fixed(millis) => millis,
+        CandleStep::Week => WEEK_MS,
+        CandleStep::Month => 30 * DAY_MS,
+    }
+}
+
+/// Open time of the candle containing `time`, aligned to UTC boundaries.
+#[uniffi::export]
+pub fn candle_open_time(interval: CandleInterval, time: u64) -> u64 {
+    interval.step().open_of(time)
+}
+
+/// Open time of the candle after the one opening at `open`.
+#[uniffi::export]
+pub fn next_candle_open(interval: CandleInterval, open: u64) -> u64 {
+    interval.step().next(open)
+}
+
+/// How consecutive candle open times are spaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CandleStep {
+    // Aligned to multiples of the step since the epoch
+    Fixed(u64),
+    // Weeks opening on Mondays at 00:00 UTC
+    Week,
+    // Calendar months, opening on the 1st at 00:00 UTC
+    Month,
+}
+
+impl CandleStep {
+    pub(crate) fn open_of(self, time: u64) -> u64 {
+        match self {
+            Self::Fixed(millis) => time / millis * millis,
+            Self::Week => Self::Fixed(WEEK_MS).open_of(time + WEEK_OFFSET_MS).saturating_sub(WEEK_OFFSET_MS),
+            Self::Month => {
+                let date = DateTime::<Utc>::from_timestamp_millis(time as i64).unwrap_or_default();
+                let first = date.date_naive().with_day(1).unwrap_or_default().and_time(NaiveTime::MIN).and_utc();
+                first.timestamp_millis().max(0) as u64
+            }
+        }
+    }
+
+    // Longest a single step can be, used to size request chunks
+    fn max_millis(self) -> u64 {
+        match self {
+            Self::Fixed(millis) => millis,
+            Self::Week => WEEK_MS,
+            Self::Month => 31 * DAY_MS,
+        }
+    }
+
+    pub(crate) fn next(self, open: u64) -> u64 {
+        match self {
+            Self::Fixed(millis) => open + millis,
+            Self::Week => Self::Fixed(WEEK_MS).next(open),
+            Self::Month => shift_months(open, 1),
+        }
+    }
+
+    fn prev(self, open: u64) -> u64 {
+        match self {
+            Self::Fixed(millis) => open.saturating_sub(millis),
+            Self::Week => Self::Fixed(WEEK_MS).prev(open),
+            Self::Month => shift_months(open, -1),
+        }
+    }
+
+    // Number of opens in [from, to)
+    fn count(self, from: u64, to: u64) -> u64 {
+        match self {
+            Self::Fixed(millis) => to.saturating_sub(from).div_ceil(millis),
+            Self::Week => Self::Fixed(WEEK_MS).count(from, to),
+            Self::Month => {
+                let (mut open, mut count) = (from, 0);
+                while open < to {
+                    open = shift_months(open, 1);
+                    count += 1;
+                }
+                count
+            }
+        }
+    }
+
+    fn advance(self, open: u64, steps: u64) -> u64 {
+        match self {
+            Self::Fixed(millis) => open + steps * millis,
+            Self::Week => Self::Fixed(WEEK_MS).advance(open, steps),
+            Self::Month => (0..steps).fold(open, |open, _| shift_months(open, 1)),
+        }
+    }
+}
+
+fn shift_months(time: u64, months: i32) -> u64 {
+    let date = DateTime::<Utc>::from_timestamp_millis(time as i64).unwrap_or_default();
+    let shifted = if months >= 0 {
+        date.checked_add_months(Months::new(months as u32))
+    } else {
+        date.checked_sub_months(Months::new(months.unsigned_abs()))
+    };
+    shifted.map_or(0, |shifted| shifted.timestamp_millis().max(0) as u64)
+}
+
+/// Inclusive [start, end] windows that each fit in one candleSnapshot request.
+pub(crate) fn candle_chunks(step: CandleStep, start_time: u64, end_time: u64) -> Vec<(u64, u64)> {
+    let span = MAX_CANDLES_PER_REQUEST * step.max_millis();
+    let mut chunks = Vec::new();
+    let mut chunk_start = start_time;
+    while chunk_start <= end_time {
+        let chunk_end = chunk_start.saturating_add(span - 1).min(end_time);
+        chunks.push((chunk_start, chunk_end));
+        let Some(next_start) = chunk_end.checked_add(1) else {
+            break;
+        };
+        chunk_start = next_start;
+    }
+    chunks
+}
+
+/// Opens missing from `opens` (sorted, deduplicated) within [start, end], as
+/// (first missing open, last missing open, count).
+///
+/// Expected opens are anchored on the candles that did arrive, so gaps do not
+/// depend on how the exchange aligns each interval.
+pub(crate) fn find_gaps(step: CandleStep, opens: &[u64], start_time: u64, end_time: u64) -> Vec<(u64, u64, u64)> {
+    let (Some(&first), Some(&last)) = (opens.first(), opens.last()) else {
+        // Every candle opening in the range is missing, from the first open at or after the start
+        let mut first = step.open_of(start_time);
+        if first < start_time {
+            first = step.next(first);
+        }
+        return if first <= end_time {
+            let count = step.count(first, end_time + 1);
+            vec![(first, step.advance(first, count - 1), count)]
+        } else {
+            Vec::new()
+        };
+    };
+
+    let mut gaps = Vec::new();
+
+    // Before the first candle, counting back to the range start
+    let mut earliest = first;
+    let mut leading = 0;
+    while step.prev(earliest) >= start_time && step.prev(earliest) < earliest {
+        earliest = step.prev(earliest);
+        leading += 1;
+    }
+    if leading > 0 {
+        gaps.push((earliest, step.prev(first), leading));
+    }
+
+    for pair in opens.windows(2) {
+        let expected = step.next(pair[0]);
+        if expected < pair[1] {
+            let count = step.count(expected, pair[1]);
+            gaps.push((expected, step.advance(expected, count - 1), count));
+        }
+    }
+
+    let expected = step.next(last);
+    if expected <= end_time {
+        let count = step.count(expected, end_time + 1);
+        gaps.push((expected, step.advance(expected, count - 1), count));
+    }
+    gaps
+}
+
+/// Aggregates candles into a larger interval, e.g. 1h into 4h or 1w.
+///
+/// Buckets with no candles are left out, and buckets with missing candles are
+/// built from the ones present. Candles longer than the target interval cannot
+/// be split and are dropped.
+#[uniffi::export]
+pub fn resample_candles(candles: Vec<CandleData>, interval: CandleInterval) -> Vec<CandleData> {
+    resample(candles, interval.step(), interval.as_str())
+}
+
+/// Like `resample_candles`, for intervals the API does not serve such as 2m;
+/// buckets are aligned to multiples of the interval since the epoch, so unlike
+/// `OneWeek`, 10080 minutes opens on Thursdays.
+#[uniffi::export]
+pub fn resample_candles_minutes(candles: Vec<CandleData>, minutes: u32) -> Vec<CandleData> {
+    if minutes == 0 {
+        return Vec::new();
+    }
+    resample(candles, CandleStep::Fixed(minutes as u64 * MINUTE_MS), &format!("{}m", minutes))
+}
+
+fn resample(mut candles: Vec<CandleData>, step: CandleStep, interval: &str) -> Vec<CandleData> {
+    candles.sort_by_key(|candle| candle.time_open);
+    candles.dedup_by_key(|candle| candle.time_open);
+
+    let mut resampled: Vec<CandleData> = Vec::new();
+    for candle in candles {
+        let bucket_open = step.open_of(candle.time_open);
+        let bucket_close = step.next(bucket_open) - 1;
+        if candle.time_close > bucket_close {
+            continue;
+        }
+
+        match resampled.last_mut() {
+            Some(bucket) if bucket.time_open == bucket_open => {
+                if number(&candle.high) > number(&bucket.high) {
+                    bucket.high = candle.high;
+                }
+                if number(&candle.low) < number(&bucket.low) {
+                    bucket.low = candle.low;
+                }
+                bucket.close = candle.close;
+                bucket.volume = add_decimals(&bucket.volume, &candle.volume);
+                bucket.num_trades += candle.num_trades;
+            }
+            _ => resampled.push(CandleData {
+                time_open: bucket_open,
+                time_close: bucket_close,
+                interval: interval.to_string(),
+                ..candle
+            }),
+        }
+    }
+    resampled
+}
+
+// Sums keep the precision of their inputs rather than f64 noise like 1.2000000000000002
+fn add_decimals(a: &str, b: &str) -> String {
+    let decimals = |value: &str| value.split_once('.').map_or(0, |(_, fraction)| fraction.len());
+    format!("{:.*}", decimals(a).max(decimals(b)), number(a) + number(b))
+}
@@ -0,0 +1,458 @@
+use crate::metadata::MetadataCache;
+use crate::rate_limit::{action_weight, info_weight, limiter};
+use crate::signer::{sign_digest, signer_address, Signer};
+use ethers::abi::{encode, ParamType, Token, Tokenizable};
+use ethers::types::transaction::eip712::{self, encode_eip712_type, EIP712Domain, Eip712, Eip712Error};
+use ethers::types::{Signature, H160, H256, U256};
+use ethers::utils::keccak256;
+use hyperliquid_rust_sdk::{BuilderInfo, ClientCancelRequest, ClientOrder, ClientOrderRequest, Error, ExchangeResponseStatus, InfoClient, SpotSend, UsdSend, Withdraw3};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+// Wire format of exchange actions. Field order and renames of L1 actions must
+// match the exchange's msgpack encoding exactly, since the action hash is signed.
+// Transfers to other users are instead signed directly as EIP-712 typed data.
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum Action {
+    Order(BulkOrder),
+    Cancel(BulkCancel),
+    CreateSubAccount(CreateSubAccount),
+    SubAccountTransfer(SubAccountTransfer),
+    SubAccountSpotTransfer(SubAccountSpotTransfer),
+    VaultTransfer(VaultTransfer),
+    UsdSend(UsdSend),
+    SpotSend(SpotSend),
+    Withdraw3(Withdraw3),
+    ApproveBuilderFee(ApproveBuilderFee),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct BulkOrder {
+    pub orders: Vec<OrderWire>,
+    pub grouping: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub builder: Option<BuilderInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct OrderWire {
+    #[serde(rename = "a")]
+    pub asset: u32,
+    #[serde(rename = "b")]
+    pub is_buy: bool,
+    #[serde(rename = "p")]
+    pub limit_px: String,
+    #[serde(rename = "s")]
+    pub sz: String,
+    #[serde(rename = "r")]
+    pub reduce_only: bool,
+    #[serde(rename = "t")]
+    pub order_type: OrderTypeWire,
+    #[serde(rename = "c", skip_serializing_if = "Option::is_none")]
+    pub cloid: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum OrderTypeWire {
+    Limit {
+        tif: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Trigger {
+        is_market: bool,
+        trigger_px: String,
+        tpsl: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct BulkCancel {
+    pub cancels: Vec<CancelWire>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct CancelWire {
+    #[serde(rename = "a")]
+    pub asset: u32,
+    #[serde(rename = "o")]
+    pub oid: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct CreateSubAccount {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubAccountTransfer {
+    pub sub_account_user: String,
+    pub is_deposit: bool,
+    // Micro-USDC (6 decimals)
+    pub usd: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SubAccountSpotTransfer {
+    pub sub_account_user: String,
+    pub is_deposit: bool,
+    // "NAME:0x<token id>"
+    pub token: String,
+    pub amount: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct VaultTransfer {
+    pub vault_address: String,
+    pub is_deposit: bool,
+    // Micro-USDC (6 decimals)
+    pub usd: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApproveBuilderFee {
+    pub signature_chain_id: U256,
+    pub hyperliquid_chain: String,
+    // Percentage string, e.g. "0.01%"
+    pub max_fee_rate: String,
+    pub builder: String,
+    pub nonce: u64,
+}
+
+// The SDK defines this action but signs it as an L1 action, which the
+// exchange rejects; it is a user-signed action like the transfers.
+impl Eip712 for ApproveBuilderFee {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(EIP712Domain {
+            name: Some("HyperliquidSignTransaction".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(self.signature_chain_id),
+            verifying_contract: Some(H160::zero()),
+            salt: None,
+        })
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(eip712::make_type_hash(
+            "HyperliquidTransaction:ApproveBuilderFee".to_string(),
+            &[
+                ("hyperliquidChain".to_string(), ParamType::String),
+                ("maxFeeRate".to_string(), ParamType::String),
+                ("builder".to_string(), ParamType::Address),
+                ("nonce".to_string(), ParamType::Uint(64)),
+            ],
+        ))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        let builder: H160 = self
+            .builder
+            .parse()
+            .map_err(|_| Eip712Error::Message(format!("Invalid builder address: {}", self.builder)))?;
+        let items = vec![
+            Token::Uint(Self::type_hash()?.into()),
+            encode_eip712_type(self.hyperliquid_chain.clone().into_token()),
+            encode_eip712_type(self.max_fee_rate.clone().into_token()),
+            encode_eip712_type(builder.into_token()),
+            encode_eip712_type(self.nonce.into_token()),
+        ];
+        Ok(keccak256(encode(&items)))
+    }
+}
+
+impl Action {
+    pub(crate) fn hash(&self, nonce: u64, vault_address: Option<H160>) -> Result<H256, Error> {
+        let mut bytes = rmp_serde::to_vec_named(self).map_err(|e| Error::RmpParse(e.to_string()))?;
+        bytes.extend(nonce.to_be_bytes());
+        match vault_address {
+            Some(vault_address) => {
+                bytes.push(1);
+                bytes.extend(vault_address.to_fixed_bytes());
+            }
+            None => bytes.push(0),
+        }
+        Ok(H256(keccak256(bytes)))
+    }
+
+    // User-signed actions carry their nonce in the signed fields, and the
+    // request nonce has to match it
+    pub(crate) fn user_signed_nonce(&self) -> Option<u64> {
+        match self {
+            Action::UsdSend(usd_send) => Some(usd_send.time),
+            Action::SpotSend(spot_send) => Some(spot_send.time),
+            Action::Withdraw3(withdraw) => Some(withdraw.time),
+            Action::ApproveBuilderFee(approval) => Some(approval.nonce),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn batch_length(&self) -> usize {
+        match self {
+            Action::Order(order) => order.orders.len(),
+            Action::Cancel(cancel) => cancel.cancels.len(),
+            _ => 1,
+        }
+    }
+
+    pub(crate) fn is_user_signed(&self) -> bool {
+        self.user_signed_nonce().is_some()
+    }
+
+    // The 32-byte digest the signer is asked to sign for this action
+    pub(crate) fn signing_digest(&self, nonce: u64, vault_address: Option<H160>, is_mainnet: bool) -> Result<H256, Error> {
+        let typed_data = match self {
+            Action::UsdSend(usd_send) => usd_send.encode_eip712(),
+            Action::SpotSend(spot_send) => spot_send.encode_eip712(),
+            Action::Withdraw3(withdraw) => withdraw.encode_eip712(),
+            Action::ApproveBuilderFee(approval) => approval.encode_eip712(),
+            _ => return l1_action_digest(self.hash(nonce, vault_address)?, is_mainnet),
+        };
+        typed_data.map(H256).map_err(|e| Error::Eip712(e.to_string()))
+    }
+}
+
+// The "phantom agent" that L1 actions are signed as.
+pub(crate) struct Agent {
+    pub source: String,
+    pub connection_id: H256,
+}
+
+impl Eip712 for Agent {
+    type Error = Eip712Error;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(EIP712Domain {
+            name: Some("Exchange".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(1337.into()),
+            verifying_contract: Some(H160::zero()),
+            salt: None,
+        })
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(eip712::make_type_hash(
+            "Agent".to_string(),
+            &[
+                ("source".to_string(), ParamType::String),
+                ("connectionId".to_string(), ParamType::FixedBytes(32)),
+            ],
+        ))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        let items = vec![
+            Token::Uint(Self::type_hash()?.into()),
+            encode_eip712_type(self.source.clone().into_token()),
+            encode_eip712_type(self.connection_id.into_token()),
+        ];
+        Ok(keccak256(encode(&items)))
+    }
+}
+
+pub(crate) fn l1_action_digest(connection_id: H256, is_mainnet: bool) -> Result<H256, Error> {
+    let source = if is_mainnet { "a" } else { "b" }.to_string();
+    let agent = Agent { source, connection_id };
+    agent.encode_eip712().map(H256).map_err(|e| Error::Eip712(e.to_string()))
+}
+
+static LAST_NONCE: AtomicU64 = AtomicU64::new(0);
+
+// Millisecond timestamp, bumped when needed so that two actions sent in the
+// same millisecond never share a nonce.
+pub(crate) fn next_nonce() -> u64 {
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let mut last = LAST_NONCE.load(Ordering::Relaxed);
+    loop {
+        let next = now.max(last + 1);
+        match LAST_NONCE.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return next,
+            Err(current) => last = current,
+        }
+    }
+}
+
+// Same formatting the exchange uses when hashing prices and sizes:
+// at most 8 decimals, no trailing zeros.
+pub(crate) fn float_to_wire(x: f64) -> String {
+    let mut s = format!("{:.8}", x);
+    while s.ends_with('0') {
+        s.pop();
+    }
+    if s.ends_with('.') {
+        s.pop();
+    }
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s
+    }
+}
+
+// Largest builder fee the exchange accepts: 1% on spot, 0.1% on perps
+pub(crate) const MAX_BUILDER_FEE_TENTHS_BPS: u32 = 1000;
+pub(crate) const MAX_PERP_BUILDER_FEE_TENTHS_BPS: u32 = 100;
+
+pub(crate) fn max_builder_fee_tenths_bps(is_spot: bool) -> u32 {
+    if is_spot {
+        MAX_BUILDER_FEE_TENTHS_BPS
+    } else {
+        MAX_PERP_BUILDER_FEE_TENTHS_BPS
+    }
+}
+
+// Builder fees are given in tenths of a basis point, approvals as a percentage
+pub(crate) fn builder_fee_rate(fee_tenths_bps: u32) -> String {
+    format!("{}%", float_to_wire(fee_tenths_bps as f64 / 1000.0))
+}
+
+// Chain id user-signed actions are signed against, whatever the network
+pub(crate) const SIGNATURE_CHAIN_ID: u64 = 421614;
+
+pub(crate) fn hyperliquid_chain(is_mainnet: bool) -> String {
+    if is_mainnet { "Mainnet" } else { "Testnet" }.to_string()
+}
+
+// Body of a POST to /exchange
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExchangePayload {
+    pub action: serde_json::Value,
+    pub signature: Signature,
+    pub nonce: u64,
+    pub vault_address: Option<H160>,
+}
+
+pub(crate) fn sign_payload(
+    action: &Action,
+    nonce: u64,
+    vault_address: Option<H160>,
+    is_mainnet: bool,
+    signer: &dyn Signer,
+) -> Result<ExchangePayload, Error> {
+    let digest = action.signing_digest(nonce, vault_address, is_mainnet)?;
+    let signature = sign_digest(signer, digest).map_err(|e| Error::SignatureFailure(e.to_string()))?;
+
+    Ok(ExchangePayload {
+        action: serde_json::to_value(action).map_err(|e| Error::JsonParse(e.to_string()))?,
+        signature,
+        nonce,
+        vault_address,
+    })
+}
+
+pub(crate) struct ExchangeSender {
+    info: Arc<InfoClient>,
+    signer: Arc<dyn Signer>,
+    address: H160,
+    metadata: Arc<MetadataCache>,
+}
+
+impl ExchangeSender {
+    pub(crate) async fn new(info: Arc<InfoClient>, signer: Arc<dyn Signer>, metadata: Arc<MetadataCache>) -> Result<Self, Error> {
+        let address = signer_address(signer.as_ref()).map_err(|e| Error::Wallet(e.to_string()))?;
+
+        // Seed the address budget; without it actions are only paced per IP
+        limiter().acquire(info_weight("userRateLimit")).await;
+        let request = serde_json::json!({ "type": "userRateLimit", "user": format!("{:#x}", address) });
+        if let Ok(response) = info.http_client.post("/info", request.to_string()).await {
+            if let Ok(rate_limit) = serde_json::from_str::<serde_json::Value>(&response) {
+                let used = rate_limit["nRequestsUsed"].as_u64().unwrap_or(0);
+                let cap = rate_limit["nRequestsCap"].as_u64().unwrap_or(0);
+                limiter().set_address_budget(address, cap.saturating_sub(used));
+            }
+        }
+
+        Ok(Self {
+            info,
+            signer,
+            address,
+            metadata,
+        })
+    }
+
+    pub(crate) fn address(&self) -> H160 {
+        self.address
+    }
+
+    pub(crate) fn is_mainnet(&self) -> bool {
+        self.info.http_client.is_mainnet()
+    }
+
+    pub(crate) async fn asset_index(&self, coin: &str) -> Result<u32, Error> {
+        self.metadata.get().await?.asset_index(coin).ok_or(Error::AssetNotFound)
+    }
+
+    pub(crate) async fn order_wire(&self, order: ClientOrderRequest) -> Result<OrderWire, Error> {
+        let order_type = match order.order_type {
+            ClientOrder::Limit(limit) => OrderTypeWire::Limit { tif: limit.tif },
+            ClientOrder::Trigger(trigger) => OrderTypeWire::Trigger {
+                is_market: trigger.is_market,
+                trigger_px: float_to_wire(trigger.trigger_px),
+                tpsl: trigger.tpsl,
+            },
+        };
+
+        Ok(OrderWire {
+            asset: self.asset_index(&order.asset).await?,
+            is_buy: order.is_buy,
+            limit_px: float_to_wire(order.limit_px),
+            sz: float_to_wire(order.sz),
+            reduce_only: order.reduce_only,
+            order_type,
+            cloid: order.cloid.map(|cloid| format!("0x{}", cloid.simple())),
+        })
+    }
+
+    // `vault_address` trades on behalf of a sub-account or vault the signer controls,
+    // `builder` routes a fee to a builder the user has approved
+    pub(crate) async fn order(
+        &self,
+        order: ClientOrderRequest,
+        vault_address: Option<H160>,
+        builder: Option<BuilderInfo>,
+    ) -> Result<ExchangeResponseStatus, Error> {
+        let action = Action::Order(BulkOrder {
+            orders: vec![self.order_wire(order).await?],
+            grouping: "na".to_string(),
+            builder,
+        });
+        self.post_l1_action(action, vault_address).await
+    }
+
+    pub(crate) async fn cancel(&self, cancel: ClientCancelRequest, vault_address: Option<H160>) -> Result<ExchangeResponseStatus, Error> {
+        let action = Action::Cancel(BulkCancel {
+            cancels: vec![CancelWire {
+                asset: self.asset_index(&cancel.asset).await?,
+                oid: cancel.oid,
+            }],
+        });
+        self.post_l1_action(action, vault_address).await
+    }
+
+    pub(crate) async fn post_l1_action(&self, action: Action, vault_address: Option<H160>) -> Result<ExchangeResponseStatus, Error> {
+        let response = self.post_l1_action_raw(action, vault_address).await?;
+        serde_json::from_value(response).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
+    // For actions whose response `data` is not a list of order statuses.
+    // Never retried: a timed out order may still have reached the book.
+    pub(crate) async fn post_l1_action_raw(&self, action: Action, vault_address: Option<H160>) -> Result<serde_json::Value, Error> {
+        limiter().acquire_action(self.address, action_weight(action.batch_length())).await;
+        let nonce = action.user_signed_nonce().unwrap_or_else(next_nonce);
+        let payload = sign_payload(&action, nonce, vault_address, self.is_mainnet(), self.signer.as_ref())?;
+        let body = serde_json::to_string(&payload).map_err(|e| Error::JsonParse(e.to_string()))?;
+
+        let response = self.info.http_client.post("/exchange", body).await?;
+        serde_json::from_str(&response).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+}
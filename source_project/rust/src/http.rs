@@ -0,0 +1,80 @@
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum HttpConfigError {
+    #[error("Invalid HTTP config: {reason}")]
+    Invalid { reason: String },
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum RequestError {
+    #[error("Request failed: {reason}")]
+    Failed { reason: String },
+}
+
+impl From<hyperliquid_rust_sdk::Error> for RequestError {
+    fn from(error: hyperliquid_rust_sdk::Error) -> Self {
+        RequestError::Failed { reason: error.to_string() }
+    }
+}
+
+/// Settings for the one HTTP client a `HyperliquidClient` uses for every request.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct HttpConfig {
+    pub connect_timeout_ms: u64,
+    /// Whole request, including reading the response body.
+    pub request_timeout_ms: u64,
+    /// e.g. "http://proxy.local:8080", used for both HTTP and HTTPS
+    pub proxy_url: Option<String>,
+    pub user_agent: Option<String>,
+    pub headers: HashMap<String, String>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: 10_000,
+            request_timeout_ms: 30_000,
+            proxy_url: None,
+            user_agent: None,
+            headers: HashMap::new(),
+        }
+    }
+}
+
+/// Defaults to start from when only a few settings need changing.
+#[uniffi::export]
+pub fn default_http_config() -> HttpConfig {
+    HttpConfig::default()
+}
+
+pub(crate) fn build_http_client(config: &HttpConfig) -> Result<reqwest::Client, HttpConfigError> {
+    build_client(config).map_err(|reason| HttpConfigError::Invalid { reason })
+}
+
+fn build_client(config: &HttpConfig) -> Result<reqwest::Client, String> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| format!("Invalid header name {}: {}", name, e))?;
+        let value = HeaderValue::from_str(value).map_err(|e| format!("Invalid value for header {}: {}", name, e))?;
+        headers.insert(name, value);
+    }
+
+    let user_agent = config
+        .user_agent
+        .clone()
+        .unwrap_or_else(|| format!("{}/{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")));
+    let mut builder = reqwest::Client::builder()
+        .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
+        .timeout(Duration::from_millis(config.request_timeout_ms))
+        .user_agent(user_agent)
+        .default_headers(headers);
+    if let Some(proxy_url) = &config.proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url).map_err(|e| format!("Invalid proxy URL {}: {}", proxy_url, e))?;
+        builder = builder.proxy(proxy);
+    }
+
+    builder.build().map_err(|e| format!("{}", e))
+}
@@ -0,0 +1,275 @@
+use crate::{number, CandleData};
+
+// Every point carries the time_open of the candle it was computed at. Series
+// start once enough candles are available, so the first `period - 1` or so
+// candles have no point.
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct IndicatorPoint {
+    pub time: u64,
+    pub value: f64,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct MacdPoint {
+    pub time: u64,
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct BollingerPoint {
+    pub time: u64,
+    pub middle: f64,
+    pub upper: f64,
+    pub lower: f64,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct StochasticPoint {
+    pub time: u64,
+    pub k: f64,
+    pub d: f64,
+}
+
+struct Bar {
+    time: u64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+}
+
+fn bars(candles: &[CandleData]) -> Vec<Bar> {
+    candles
+        .iter()
+        .map(|candle| Bar {
+            time: candle.time_open,
+            high: number(&candle.high),
+            low: number(&candle.low),
+            close: number(&candle.close),
+            volume: number(&candle.volume),
+        })
+        .collect()
+}
+
+fn closes(candles: &[CandleData]) -> (Vec<u64>, Vec<f64>) {
+    bars(candles).iter().map(|bar| (bar.time, bar.close)).unzip()
+}
+
+fn points(times: &[u64], values: Vec<Option<f64>>) -> Vec<IndicatorPoint> {
+    times
+        .iter()
+        .zip(values)
+        .filter_map(|(&time, value)| Some(IndicatorPoint { time, value: value? }))
+        .collect()
+}
+
+fn sma_values(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 {
+        return result;
+    }
+    let mut sum = 0.0;
+    for (i, value) in values.iter().enumerate() {
+        sum += value;
+        if i >= period {
+            sum -= values[i - period];
+        }
+        if i + 1 >= period {
+            result[i] = Some(sum / period as f64);
+        }
+    }
+    result
+}
+
+// Seeded with the SMA of the first `period` values
+fn ema_values(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() < period {
+        return result;
+    }
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut ema = values[..period].iter().sum::<f64>() / period as f64;
+    result[period - 1] = Some(ema);
+    for i in period..values.len() {
+        ema += alpha * (values[i] - ema);
+        result[i] = Some(ema);
+    }
+    result
+}
+
+// Wilder's smoothing of values[1..]: a plain average of the first `period`,
+// then avg = (avg * (period - 1) + value) / period
+fn wilder_values(values: &[f64], period: usize) -> Vec<Option<f64>> {
+    let mut result = vec![None; values.len()];
+    if period == 0 || values.len() <= period {
+        return result;
+    }
+    let mut average = values[1..=period].iter().sum::<f64>() / period as f64;
+    result[period] = Some(average);
+    for i in period + 1..values.len() {
+        average = (average * (period as f64 - 1.0) + values[i]) / period as f64;
+        result[i] = Some(average);
+    }
+    result
+}
+
+/// Simple moving average of closes.
+#[uniffi::export]
+pub fn sma(candles: Vec<CandleData>, period: u32) -> Vec<IndicatorPoint> {
+    let (times, closes) = closes(&candles);
+    points(&times, sma_values(&closes, period as usize))
+}
+
+/// Exponential moving average of closes, starting from the SMA of the first `period`.
+#[uniffi::export]
+pub fn ema(candles: Vec<CandleData>, period: u32) -> Vec<IndicatorPoint> {
+    let (times, closes) = closes(&candles);
+    points(&times, ema_values(&closes, period as usize))
+}
+
+/// Wilder's relative strength index, 0 to 100; usually over 14 candles.
+#[uniffi::export]
+pub fn rsi(candles: Vec<CandleData>, period: u32) -> Vec<IndicatorPoint> {
+    let (times, closes) = closes(&candles);
+    let mut gains = vec![0.0; closes.len()];
+    let mut losses = vec![0.0; closes.len()];
+    for i in 1..closes.len() {
+        let change = closes[i] - closes[i - 1];
+        gains[i] = change.max(0.0);
+        losses[i] = (-change).max(0.0);
+    }
+
+    let average_gains = wilder_values(&gains, period as usize);
+    let average_losses = wilder_values(&losses, period as usize);
+    let values = average_gains
+        .into_iter()
+        .zip(average_losses)
+        .map(|(gain, loss)| {
+            let (gain, loss) = (gain?, loss?);
+            // No losses at all is maximally overbought
+            Some(if loss == 0.0 { 100.0 } else { 100.0 - 100.0 / (1.0 + gain / loss) })
+        })
+        .collect();
+    points(&times, values)
+}
+
+/// MACD line (fast EMA - slow EMA), its signal EMA and their difference;
+/// usually 12, 26 and 9.
+#[uniffi::export]
+pub fn macd(candles: Vec<CandleData>, fast_period: u32, slow_period: u32, signal_period: u32) -> Vec<MacdPoint> {
+    let (times, closes) = closes(&candles);
+    let fast = ema_values(&closes, fast_period as usize);
+    let slow = ema_values(&closes, slow_period as usize);
+    let macd_line: Vec<(u64, f64)> = times
+        .iter()
+        .zip(fast.iter().zip(&slow))
+        .filter_map(|(&time, (fast, slow))| Some((time, (*fast)? - (*slow)?)))
+        .collect();
+
+    let macd_values: Vec<f64> = macd_line.iter().map(|(_, value)| *value).collect();
+    let signal = ema_values(&macd_values, signal_period as usize);
+    macd_line
+        .iter()
+        .zip(signal)
+        .filter_map(|(&(time, macd), signal)| {
+            let signal = signal?;
+            Some(MacdPoint {
+                time,
+                macd,
+                signal,
+                histogram: macd - signal,
+            })
+        })
+        .collect()
+}
+
+/// SMA of closes with bands `std_devs` population standard deviations away;
+/// usually 20 and 2.
+#[uniffi::export]
+pub fn bollinger_bands(candles: Vec<CandleData>, period: u32, std_devs: f64) -> Vec<BollingerPoint> {
+    let (times, closes) = closes(&candles);
+    let period = period as usize;
+    sma_values(&closes, period)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, middle)| {
+            let middle = middle?;
+            let window = &closes[i + 1 - period..=i];
+            let variance = window.iter().map(|close| (close - middle).powi(2)).sum::<f64>() / period as f64;
+            let width = std_devs * variance.sqrt();
+            Some(BollingerPoint {
+                time: times[i],
+                middle,
+                upper: middle + width,
+                lower: middle - width,
+            })
+        })
+        .collect()
+}
+
+/// Wilder's average true range; usually over 14 candles.
+#[uniffi::export]
+pub fn atr(candles: Vec<CandleData>, period: u32) -> Vec<IndicatorPoint> {
+    let bars = bars(&candles);
+    let mut true_ranges = vec![0.0; bars.len()];
+    for i in 1..bars.len() {
+        let previous_close = bars[i - 1].close;
+        true_ranges[i] = (bars[i].high - bars[i].low)
+            .max((bars[i].high - previous_close).abs())
+            .max((bars[i].low - previous_close).abs());
+    }
+    let times: Vec<u64> = bars.iter().map(|bar| bar.time).collect();
+    points(&times, wilder_values(&true_ranges, period as usize))
+}
+
+/// Volume-weighted average of typical price (high + low + close) / 3, anchored
+/// at the first candle; pass one session's candles for a session VWAP.
+#[uniffi::export]
+pub fn vwap(candles: Vec<CandleData>) -> Vec<IndicatorPoint> {
+    let mut weighted_sum = 0.0;
+    let mut total_volume = 0.0;
+    bars(&candles)
+        .iter()
+        .filter_map(|bar| {
+            weighted_sum += (bar.high + bar.low + bar.close) / 3.0 * bar.volume;
+            total_volume += bar.volume;
+            // Nothing traded yet, so there is no price to weight
+            (total_volume > 0.0).then(|| IndicatorPoint {
+                time: bar.time,
+                value: weighted_sum / total_volume,
+            })
+        })
+        .collect()
+}
+
+/// Stochastic oscillator: %K over `k_period` candles and %D, its SMA over
+/// `d_period`; usually 14 and 3.
+#[uniffi::export]
+pub fn stochastic(candles: Vec<CandleData>, k_period: u32, d_period: u32) -> Vec<StochasticPoint> {
+    let bars = bars(&candles);
+    let k_period = k_period as usize;
+    if k_period == 0 {
+        return Vec::new();
+    }
+
+    let k_line: Vec<(u64, f64)> = (k_period.saturating_sub(1)..bars.len())
+        .map(|i| {
+            let window = &bars[i + 1 - k_period..=i];
+            let highest = window.iter().map(|bar| bar.high).fold(f64::MIN, f64::max);
+            let lowest = window.iter().map(|bar| bar.low).fold(f64::MAX, f64::min);
+            // A flat window sits in the middle of its (empty) range
+            let k = if highest > lowest { (bars[i].close - lowest) / (highest - lowest) * 100.0 } else { 50.0 };
+            (bars[i].time, k)
+        })
+        .collect();
+
+    let k_values: Vec<f64> = k_line.iter().map(|(_, k)| *k).collect();
+    k_line
+        .iter()
+        .zip(sma_values(&k_values, d_period as usize))
+        .filter_map(|(&(time, k), d)| Some(StochasticPoint { time, k, d: d? }))
+        .collect()
+}
@@ -0,0 +1,164 @@
+use crate::signer::{LocalSigner, Signer, SignerError};
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use eth_keystore::{CipherparamsJson, CryptoJson, EthKeystore, KdfType, KdfparamsType};
+use ethers::utils::{hex, keccak256};
+use hmac::Hmac;
+use rand::RngCore;
+use sha2::Sha256;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+// Same scrypt parameters geth and MetaMask use for new keystores.
+const SCRYPT_LOG_N: u8 = 13;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const DKLEN: u8 = 32;
+
+// Upper bounds on imported KDF work so a hostile keystore cannot hang the app
+// or exhaust its memory. Both are well above what wallets write.
+const MAX_SCRYPT_N: u32 = 1 << 20;
+const MAX_SCRYPT_R_TIMES_P: u64 = 1 << 30;
+const MAX_PBKDF2_ROUNDS: u32 = 10_000_000;
+
+#[derive(uniffi::Record)]
+pub struct KeystoreInfo {
+    pub address: String,
+    pub json: String,
+}
+
+/// Generates a fresh private key and returns it only in encrypted form.
+#[uniffi::export]
+pub fn create_keystore(password: String) -> Result<KeystoreInfo, SignerError> {
+    let mut private_key = Zeroizing::new([0u8; 32]);
+    rand::thread_rng().fill_bytes(private_key.as_mut());
+    let private_key = Zeroizing::new(hex::encode(private_key.as_ref()));
+
+    let address = LocalSigner::new(&private_key)?.address();
+    let json = encrypt_private_key(&private_key, &password)?;
+    Ok(KeystoreInfo { address, json })
+}
+
+/// Encrypts an existing hex private key into Web3 Secret Storage JSON.
+#[uniffi::export]
+pub fn export_keystore(private_key: String, password: String) -> Result<String, SignerError> {
+    // Parse first so garbage never gets encrypted
+    LocalSigner::new(&private_key)?;
+    encrypt_private_key(&private_key, &password)
+}
+
+/// Decrypts keystore JSON into a signer; the private key never leaves Rust.
+#[uniffi::export]
+pub fn import_keystore(json: String, password: String) -> Result<Arc<dyn Signer>, SignerError> {
+    Ok(Arc::new(decrypt_keystore(&json, &password)?))
+}
+
+pub(crate) fn decrypt_keystore(json: &str, password: &str) -> Result<LocalSigner, SignerError> {
+    let keystore: EthKeystore = serde_json::from_str(json).map_err(|e| invalid_keystore(format!("Malformed keystore JSON: {}", e)))?;
+
+    if keystore.crypto.cipher != "aes-128-ctr" {
+        return Err(invalid_keystore(format!("Unsupported cipher: {}", keystore.crypto.cipher)));
+    }
+
+    let key = match &keystore.crypto.kdfparams {
+        KdfparamsType::Pbkdf2 { c, dklen, prf, salt } => {
+            if prf != "hmac-sha256" {
+                return Err(invalid_keystore(format!("Unsupported PBKDF2 PRF: {}", prf)));
+            }
+            if *c > MAX_PBKDF2_ROUNDS {
+                return Err(invalid_keystore(format!("PBKDF2 iteration count too large: {}", c)));
+            }
+            let mut key = Zeroizing::new(vec![0u8; *dklen as usize]);
+            pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt, *c, &mut key);
+            key
+        }
+        KdfparamsType::Scrypt { dklen, n, p, r, salt } => {
+            if !n.is_power_of_two() {
+                return Err(invalid_keystore(format!("Invalid scrypt n: {}", n)));
+            }
+            if *n > MAX_SCRYPT_N || *r as u64 * *p as u64 > MAX_SCRYPT_R_TIMES_P {
+                return Err(invalid_keystore(format!("Scrypt parameters too large: n={} r={} p={}", n, r, p)));
+            }
+            let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p)
+                .map_err(|e| invalid_keystore(format!("Invalid scrypt params: {}", e)))?;
+            let mut key = Zeroizing::new(vec![0u8; *dklen as usize]);
+            scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+                .map_err(|e| invalid_keystore(format!("Invalid scrypt output length: {}", e)))?;
+            key
+        }
+    };
+    if key.len() < 32 {
+        return Err(invalid_keystore(format!("Derived key too short: {} bytes", key.len())));
+    }
+
+    // A MAC mismatch is what a wrong password looks like
+    if !bool::from(keystore_mac(&key, &keystore.crypto.ciphertext).ct_eq(keystore.crypto.mac.as_slice())) {
+        return Err(SignerError::InvalidKeystore {
+            reason: "Incorrect password".to_string(),
+        });
+    }
+
+    let mut private_key = Zeroizing::new(keystore.crypto.ciphertext.clone());
+    let mut cipher = Aes128Ctr::new_from_slices(&key[..16], &keystore.crypto.cipherparams.iv)
+        .map_err(|e| invalid_keystore(format!("Invalid IV: {}", e)))?;
+    cipher.apply_keystream(&mut private_key);
+
+    LocalSigner::new(&Zeroizing::new(hex::encode(&private_key)))
+}
+
+fn encrypt_private_key(private_key: &str, password: &str) -> Result<String, SignerError> {
+    let private_key = hex::decode(private_key.trim_start_matches("0x")).map_err(|e| SignerError::InvalidKey {
+        reason: format!("{}", e),
+    })?;
+
+    let mut rng = rand::thread_rng();
+    let mut salt = vec![0u8; 32];
+    rng.fill_bytes(&mut salt);
+    let mut iv = vec![0u8; 16];
+    rng.fill_bytes(&mut iv);
+
+    let params = scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)
+        .map_err(|e| invalid_keystore(format!("Invalid scrypt params: {}", e)))?;
+    let mut key = Zeroizing::new(vec![0u8; DKLEN as usize]);
+    scrypt::scrypt(password.as_bytes(), &salt, &params, &mut key)
+        .map_err(|e| invalid_keystore(format!("Invalid scrypt output length: {}", e)))?;
+
+    let mut ciphertext = private_key;
+    let mut cipher = Aes128Ctr::new_from_slices(&key[..16], &iv)
+        .map_err(|e| invalid_keystore(format!("Invalid IV: {}", e)))?;
+    cipher.apply_keystream(&mut ciphertext);
+    let mac = keystore_mac(&key, &ciphertext).to_vec();
+
+    let keystore = EthKeystore {
+        id: uuid::Uuid::new_v4(),
+        version: 3,
+        crypto: CryptoJson {
+            cipher: "aes-128-ctr".to_string(),
+            cipherparams: CipherparamsJson { iv },
+            ciphertext,
+            kdf: KdfType::Scrypt,
+            kdfparams: KdfparamsType::Scrypt {
+                dklen: DKLEN,
+                n: 1 << SCRYPT_LOG_N,
+                p: SCRYPT_P,
+                r: SCRYPT_R,
+                salt,
+            },
+            mac,
+        },
+    };
+
+    serde_json::to_string(&keystore).map_err(|e| invalid_keystore(format!("Failed to serialize keystore: {}", e)))
+}
+
+fn keystore_mac(derived_key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mut input = derived_key[16..32].to_vec();
+    input.extend_from_slice(ciphertext);
+    keccak256(input)
+}
+
+fn invalid_keystore(reason: String) -> SignerError {
+    SignerError::InvalidKeystore { reason }
+}
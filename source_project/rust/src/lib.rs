@@ -1,40 +1,186 @@
 uniffi::setup_scaffolding!();
 
-use hyperliquid_rust_sdk::{BaseUrl, InfoClient, ExchangeClient, ClientOrderRequest, ClientOrder, ClientLimit, ExchangeResponseStatus, ExchangeDataStatus, ClientCancelRequest};
-use ethers::signers::{LocalWallet, Signer};
-use std::sync::Arc;
+mod candles;
+mod exchange;
+mod http;
+mod indicators;
+mod keystore;
+mod metadata;
+mod mnemonic;
+mod paper;
+mod performance;
+mod pnl;
+mod rate_limit;
+mod signer;
+mod signing;
+mod store;
+mod tax;
+mod trades;
+
+pub use candles::{
+    candle_interval_millis, candle_interval_name, candle_open_time, next_candle_open, parse_candle_interval, resample_candles,
+    resample_candles_minutes, CandleInterval,
+};
+pub use http::{default_http_config, HttpConfig, HttpConfigError, RequestError};
+pub use indicators::{
+    atr, bollinger_bands, ema, macd, rsi, sma, stochastic, vwap, BollingerPoint, IndicatorPoint, MacdPoint, StochasticPoint,
+};
+pub use keystore::{create_keystore, export_keystore, import_keystore, KeystoreInfo};
+pub use rate_limit::RetryPolicy;
+pub use mnemonic::{derive_mnemonic_accounts, generate_mnemonic, mnemonic_signer, validate_mnemonic, DerivedAccount};
+pub use paper::{default_paper_config, PaperConfig, PaperFeed};
+pub use performance::{compute_performance, PerformanceMetrics};
+pub use pnl::{compute_pnl, CoinPnl, CostBasisMethod};
+pub use signer::{new_local_signer, LocalSigner, Signer, SignerError};
+pub use signing::{build_action, sign_action, verify_action_signature, ActionRequest, SignedAction, UnsignedAction};
+pub use store::CacheError;
+pub use tax::{build_tax_report, tax_report_csv, tax_report_json, FundingPayment, LedgerEntry, TaxLot, TaxReport, TaxReportSection, TaxSummary, TaxTrade};
+pub use trades::{Trade, TradeListener, TradeSide, TradeVolume};
+
+use candles::{candle_chunks, find_gaps};
+use http::build_http_client;
+use metadata::{Metadata, MetadataCache};
+use paper::{decimal, PaperExchange, PaperMarket, PaperOrderOutcome};
+use exchange::{builder_fee_rate, hyperliquid_chain, next_nonce, Action, ApproveBuilderFee, CreateSubAccount, ExchangeSender, SubAccountSpotTransfer, SubAccountTransfer, VaultTransfer, max_builder_fee_tenths_bps, MAX_BUILDER_FEE_TENTHS_BPS, SIGNATURE_CHAIN_ID};
+use rate_limit::{info_weight, limiter, response_weight, with_retry};
+use store::{fill_key, Store};
+use tax::{build_report, parse_funding_payment, parse_ledger_entry, year_bounds};
+use trades::{from_ws_trade, parse_trade, TradeTape};
+use hyperliquid_rust_sdk::{BaseUrl, BuilderInfo, MAINNET_API_URL, InfoClient, ClientOrderRequest, ClientOrder, ClientLimit, ExchangeResponseStatus, ExchangeDataStatus, ClientCancelRequest, Message, Subscription};
+use ethers::types::H160;
+use std::future::Future;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::runtime::Runtime;
 
+// Sizes below this are rounding dust, not an order or position
+const DUST: f64 = 1e-9;
+// Internals the offline test bins check directly; not part of the API
+#[cfg(feature = "test-hooks")]
+#[doc(hidden)]
+pub mod test_hooks {
+    pub use crate::store::Store;
+}
+
+// Hyperliquidity Provider, the protocol vault
+const HLP_VAULT_ADDRESS: &str = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303";
+// fundingHistory returns at most this many entries per request
+const FUNDING_HISTORY_PAGE_SIZE: usize = 500;
+const HOURS_PER_YEAR: f64 = 24.0 * 365.0;
+// userFillsByTime returns at most this many fills per request
+const FILLS_PAGE_SIZE: usize = 2000;
+// Fills of the last few seconds may not be visible yet, so they are not cached as complete
+const FILL_SETTLE_MS: u64 = 5_000;
+// userFunding and userNonFundingLedgerUpdates return at most this many updates per request
+const LEDGER_PAGE_SIZE: usize = 500;
+// Finest first; the API only serves the latest few thousand candles of each interval
+const USD_PRICE_INTERVALS: [CandleInterval; 2] = [CandleInterval::OneHour, CandleInterval::OneDay];
+
 #[derive(uniffi::Object)]
 pub struct HyperliquidClient {
+    // Shared by the SDK clients and our own requests
+    http: reqwest::Client,
     info: Arc<InfoClient>,
-    exchange: Option<Arc<ExchangeClient>>,
+    metadata: Arc<MetadataCache>,
+    exchange: Option<Arc<ExchangeSender>>,
+    // Account the client is bound to: the signer's address, or the watched
+    // address for watch-only clients
+    address: Option<H160>,
+    watched: Mutex<Vec<H160>>,
+    // Builder fee attached to every order this client places
+    builder: Mutex<Option<BuilderInfo>>,
+    // Shared with the metadata cache, which retries its own requests
+    retry_policy: Arc<Mutex<RetryPolicy>>,
+    // Optional on-device cache of candles and fills
+    cache: Mutex<Option<Arc<Store>>>,
+    // Websocket connection, opened on the first subscription
+    streams: tokio::sync::Mutex<Option<InfoClient>>,
+    // Per coin: trade subscription id and the tape it feeds, tagged with the
+    // same id so a replaced subscription's task can tell it is stale
+    trade_subscriptions: Mutex<HashMap<String, u32>>,
+    tapes: Arc<Mutex<HashMap<String, (u32, TradeTape)>>>,
+    // Simulated exchange that takes the orders and answers account queries
+    // of a paper-trading client
+    paper: Option<Arc<PaperExchange>>,
     runtime: Arc<Runtime>,
 }
 
+impl Default for HyperliquidClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[uniffi::export]
 impl HyperliquidClient {
     #[uniffi::constructor]
     pub fn new() -> Self {
+        Self::new_with_http_config(HttpConfig::default()).expect("Failed to create HTTP client")
+    }
+    
+    #[uniffi::constructor]
+    pub fn new_with_http_config(config: HttpConfig) -> Result<Self, HttpConfigError> {
+        let http = build_http_client(&config)?;
         let runtime = Arc::new(Runtime::new().expect("Failed to create runtime"));
         let info = runtime.block_on(async {
-            InfoClient::new(None, Some(BaseUrl::Mainnet))
+            InfoClient::new(Some(http.clone()), Some(BaseUrl::Mainnet))
                 .await
                 .expect("Failed to create InfoClient")
         });
+        let info = Arc::new(info);
+        let retry_policy = Arc::new(Mutex::new(RetryPolicy::default()));
+        let metadata = MetadataCache::new(info.clone(), retry_policy.clone());
+        metadata.spawn_background_refresh(&runtime);
         
-        Self {
-            info: Arc::new(info),
+        Ok(Self {
+            http,
+            info,
+            metadata,
             exchange: None,
+            address: None,
+            watched: Mutex::new(Vec::new()),
+            builder: Mutex::new(None),
+            retry_policy,
+            cache: Mutex::new(None),
+            streams: tokio::sync::Mutex::new(None),
+            trade_subscriptions: Mutex::new(HashMap::new()),
+            tapes: Arc::new(Mutex::new(HashMap::new())),
+            paper: None,
             runtime,
-        }
+        })
+    }
+    
+    #[uniffi::constructor]
+    pub fn new_watch_only(address: String) -> Result<Self, SignerError> {
+        let addr = parse_address(&address)?;
+        let mut client = Self::new();
+        client.address = Some(addr);
+        client.watched = Mutex::new(vec![addr]);
+        Ok(client)
+    }
+    
+    /// A client trading against an in-memory simulator instead of the exchange.
+    ///
+    /// Orders, cancels, balances, positions, open orders and fills all go to the
+    /// simulated account. Market data comes from the exchange on a live feed,
+    /// and from the pushed books and mids on a recorded one.
+    #[uniffi::constructor]
+    pub fn new_paper_trading(config: PaperConfig) -> Result<Self, SignerError> {
+        let addr = match &config.address {
+            Some(address) => parse_address(address)?,
+            None => H160::zero(),
+        };
+        let mut client = Self::new();
+        client.address = Some(addr);
+        client.watched = Mutex::new(vec![addr]);
+        client.paper = Some(Arc::new(PaperExchange::new(&config)));
+        Ok(client)
     }
     
     pub fn get_exchange_meta(&self) -> ExchangeMeta {
-        let info = self.info.clone();
-        let meta = self.runtime.block_on(async move {
-            info.meta().await.expect("Failed to get meta")
-        });
+        let metadata = self.metadata();
+        let meta = &metadata.perp;
         
         let assets: Vec<AssetInfo> = meta.universe.iter()
             .take(10)
@@ -50,10 +196,169 @@ impl HyperliquidClient {
         }
     }
     
+    pub fn get_perp_asset_contexts(&self) -> Vec<AssetContext> {
+        let response = self.runtime.block_on(async move {
+            self.post_info(serde_json::json!({ "type": "metaAndAssetCtxs" })).await
+        });
+        
+        // [meta, contexts], with contexts in universe order
+        let universe = response[0]["universe"].as_array().cloned().unwrap_or_default();
+        let contexts = response[1].as_array().cloned().unwrap_or_default();
+        universe.iter().zip(contexts.iter())
+            .map(|(asset, ctx)| {
+                let name = asset["name"].as_str().unwrap_or("").to_string();
+                parse_asset_context(name.clone(), name, false, ctx)
+            })
+            .collect()
+    }
+    
+    pub fn get_spot_asset_contexts(&self) -> Vec<AssetContext> {
+        let response = self.runtime.block_on(async move {
+            self.post_info(serde_json::json!({ "type": "spotMetaAndAssetCtxs" })).await
+        });
+        
+        let metadata = self.metadata();
+        let display_names: std::collections::HashMap<String, String> = metadata.spot.universe.iter()
+            .filter_map(|pair| {
+                let base = metadata.spot.token(pair.tokens[0])?;
+                let quote = metadata.spot.token(pair.tokens[1])?;
+                Some((pair.name.clone(), format!("{}/{}", base.name, quote.name)))
+            })
+            .collect();
+        
+        response[1].as_array().cloned().unwrap_or_default().iter()
+            .map(|ctx| {
+                let coin = ctx["coin"].as_str().unwrap_or("").to_string();
+                let display_name = display_names.get(&coin).cloned().unwrap_or_else(|| coin.clone());
+                parse_asset_context(coin, display_name, true, ctx)
+            })
+            .collect()
+    }
+    
+    pub fn get_funding_history(&self, coin: String, start_time: u64, end_time: Option<u64>) -> Vec<FundingRate> {
+        let request_body = serde_json::json!({ "type": "fundingHistory", "coin": coin });
+        let entries = self.runtime.block_on(self.post_info_pages(request_body, start_time, end_time, FUNDING_HISTORY_PAGE_SIZE));
+        
+        entries.iter()
+            .map(|entry| FundingRate {
+                coin: entry["coin"].as_str().unwrap_or("").to_string(),
+                funding_rate: entry["fundingRate"].as_str().map(number).unwrap_or(0.0),
+                premium: entry["premium"].as_str().map(number).unwrap_or(0.0),
+                time: entry["time"].as_u64().unwrap_or(0),
+            })
+            .collect()
+    }
+    
+    pub fn get_predicted_fundings(&self) -> Vec<PredictedFunding> {
+        let response = self.runtime.block_on(async move {
+            self.post_info(serde_json::json!({ "type": "predictedFundings" })).await
+        });
+        
+        // [[coin, [[venue, {fundingRate, nextFundingTime, fundingIntervalHours} | null], ...]], ...]
+        response.as_array().cloned().unwrap_or_default().iter()
+            .map(|entry| PredictedFunding {
+                coin: entry[0].as_str().unwrap_or("").to_string(),
+                venues: entry[1].as_array().cloned().unwrap_or_default().iter()
+                    .filter_map(|venue| parse_venue_funding(&venue[0], &venue[1]))
+                    .collect(),
+            })
+            .collect()
+    }
+    
+    pub fn get_recent_trades(&self, coin: String) -> Vec<Trade> {
+        let response = self.runtime.block_on(async {
+            self.post_info(serde_json::json!({ "type": "recentTrades", "coin": coin })).await
+        });
+        
+        let trades: Vec<Trade> = response.as_array().cloned().unwrap_or_default().iter()
+            .map(parse_trade)
+            .collect();
+        if let Some((_, tape)) = self.tapes.lock().unwrap().get_mut(&coin) {
+            tape.add(trades.clone());
+        }
+        trades
+    }
+    
+    /// Streams new trades of `coin` to `listener` until `unsubscribe_trades`.
+    /// One subscription per coin; subscribing again replaces the listener.
+    /// `aggressor` is always None on streamed trades, see `TradeListener`.
+    pub fn subscribe_trades(&self, coin: String, listener: Arc<dyn TradeListener>) -> bool {
+        self.unsubscribe_trades(coin.clone());
+        
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let subscribed = self.runtime.block_on(async {
+            let mut streams = self.streams.lock().await;
+            if streams.is_none() {
+                *streams = Some(InfoClient::with_reconnect(Some(self.http.clone()), Some(BaseUrl::Mainnet)).await?);
+            }
+            match streams.as_mut() {
+                Some(streams) => streams.subscribe(Subscription::Trades { coin: coin.clone() }, sender).await,
+                None => Err(hyperliquid_rust_sdk::Error::WsManagerNotFound),
+            }
+        });
+        let Ok(subscription_id) = subscribed else {
+            return false;
+        };
+        self.trade_subscriptions.lock().unwrap().insert(coin.clone(), subscription_id);
+        self.tapes.lock().unwrap().insert(coin.clone(), (subscription_id, TradeTape::default()));
+        
+        let tapes = self.tapes.clone();
+        self.runtime.spawn(async move {
+            // Ends when unsubscribing drops the sender
+            while let Some(message) = receiver.recv().await {
+                let Message::Trades(trades) = message else {
+                    continue;
+                };
+                let trades = trades.data.iter().map(from_ws_trade).collect();
+                // Resubscribing puts a new tape under the same coin
+                let added = match tapes.lock().unwrap().get_mut(&coin) {
+                    Some((id, tape)) if *id == subscription_id => tape.add(trades),
+                    _ => break,
+                };
+                if !added.is_empty() {
+                    listener.on_trades(added);
+                }
+            }
+        });
+        true
+    }
+    
+    pub fn unsubscribe_trades(&self, coin: String) -> bool {
+        self.tapes.lock().unwrap().remove(&coin);
+        let Some(subscription_id) = self.trade_subscriptions.lock().unwrap().remove(&coin) else {
+            return false;
+        };
+        self.runtime.block_on(async {
+            match self.streams.lock().await.as_mut() {
+                Some(streams) => streams.unsubscribe(subscription_id).await.is_ok(),
+                None => false,
+            }
+        })
+    }
+    
+    /// Taker buy and sell volume of `coin` over the last `minutes` (at most 60).
+    /// Uses the subscribed tape, or only the most recent trades otherwise.
+    pub fn get_trade_volume(&self, coin: String, minutes: u32) -> TradeVolume {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        if let Some((_, tape)) = self.tapes.lock().unwrap().get(&coin) {
+            return tape.volume(&coin, minutes, now);
+        }
+        
+        let mut tape = TradeTape::default();
+        tape.add(self.get_recent_trades(coin.clone()));
+        tape.volume(&coin, minutes, now)
+    }
+    
     pub fn get_all_mids(&self) -> Vec<PriceInfo> {
+        if let Some(paper) = self.recorded_feed() {
+            return paper.mids().into_iter()
+                .map(|(coin, price)| PriceInfo { coin, price: decimal(price) })
+                .collect();
+        }
+        
         let info = self.info.clone();
         let all_mids = self.runtime.block_on(async move {
-            info.all_mids().await.expect("Failed to get mids")
+            self.info_call("allMids", || info.all_mids()).await.expect("Failed to get mids")
         });
         
         // Return more results and ensure BTC variants are included
@@ -87,9 +392,13 @@ impl HyperliquidClient {
     }
     
     pub fn get_btc_price(&self) -> String {
+        if self.recorded_feed().is_some() {
+            return self.btc_spot_price().map(decimal).unwrap_or_else(|_| "0.0".to_string());
+        }
+        
         let info = self.info.clone();
         let all_mids = self.runtime.block_on(async move {
-            info.all_mids().await.expect("Failed to get mids")
+            self.info_call("allMids", || info.all_mids()).await.expect("Failed to get mids")
         });
         
         // Try different BTC variants in priority order
@@ -105,9 +414,13 @@ impl HyperliquidClient {
     }
     
     pub fn get_l2_orderbook(&self, coin: String) -> OrderbookData {
+        if let Some(paper) = self.recorded_feed() {
+            return paper.book(&coin, 5).unwrap_or(OrderbookData { bids: Vec::new(), asks: Vec::new() });
+        }
+        
         let info = self.info.clone();
         let l2_data = self.runtime.block_on(async move {
-            info.l2_snapshot(coin).await.expect("Failed to get L2 snapshot")
+            self.info_call("l2Book", || info.l2_snapshot(coin.clone())).await.expect("Failed to get L2 snapshot")
         });
         
         let bids: Vec<OrderLevel> = l2_data.levels[0].iter()
@@ -131,33 +444,70 @@ impl HyperliquidClient {
     
     #[uniffi::constructor]
     pub fn new_with_wallet(private_key: String) -> Self {
+        let signer = LocalSigner::new(&private_key).expect("Invalid private key");
+        Self::new_with_signer(Arc::new(signer))
+    }
+    
+    #[uniffi::constructor]
+    pub fn new_with_keystore(json: String, password: String) -> Result<Self, SignerError> {
+        let signer = keystore::decrypt_keystore(&json, &password)?;
+        Ok(Self::new_with_signer(Arc::new(signer)))
+    }
+    
+    #[uniffi::constructor]
+    pub fn new_with_mnemonic(phrase: String, account_index: u32) -> Result<Self, SignerError> {
+        let signer = mnemonic::derive_signer(&phrase, account_index)?;
+        Ok(Self::new_with_signer(Arc::new(signer)))
+    }
+    
+    #[uniffi::constructor]
+    pub fn new_with_signer(signer: Arc<dyn Signer>) -> Self {
+        Self::new_with_signer_and_http_config(signer, HttpConfig::default()).expect("Failed to create HTTP client")
+    }
+    
+    #[uniffi::constructor]
+    pub fn new_with_signer_and_http_config(signer: Arc<dyn Signer>, config: HttpConfig) -> Result<Self, HttpConfigError> {
+        let http = build_http_client(&config)?;
         let runtime = Arc::new(Runtime::new().expect("Failed to create runtime"));
-        let (info, exchange) = runtime.block_on(async {
-            let wallet: LocalWallet = private_key.parse().expect("Invalid private key");
+        let retry_policy = Arc::new(Mutex::new(RetryPolicy::default()));
+        let (info, metadata, exchange) = runtime.block_on(async {
+            let info = Arc::new(
+                InfoClient::new(Some(http.clone()), Some(BaseUrl::Mainnet))
+                    .await
+                    .expect("Failed to create InfoClient"),
+            );
+            let metadata = MetadataCache::new(info.clone(), retry_policy.clone());
             
-            let info = InfoClient::new(None, Some(BaseUrl::Mainnet))
-                .await
-                .expect("Failed to create InfoClient");
-                
-            let exchange = ExchangeClient::new(None, wallet, Some(BaseUrl::Mainnet), None, None)
+            let exchange = ExchangeSender::new(info.clone(), signer, metadata.clone())
                 .await
-                .expect("Failed to create ExchangeClient");
+                .expect("Failed to create exchange sender");
             
-            (info, exchange)
+            (info, metadata, exchange)
         });
+        metadata.spawn_background_refresh(&runtime);
         
-        Self {
-            info: Arc::new(info),
+        let address = exchange.address();
+        Ok(Self {
+            http,
+            info,
+            metadata,
             exchange: Some(Arc::new(exchange)),
+            address: Some(address),
+            watched: Mutex::new(vec![address]),
+            builder: Mutex::new(None),
+            retry_policy,
+            cache: Mutex::new(None),
+            streams: tokio::sync::Mutex::new(None),
+            trade_subscriptions: Mutex::new(HashMap::new()),
+            tapes: Arc::new(Mutex::new(HashMap::new())),
+            paper: None,
             runtime,
-        }
+        })
     }
     
     pub fn get_spot_meta(&self) -> Vec<String> {
-        let info = self.info.clone();
-        let spot_meta = self.runtime.block_on(async move {
-            info.spot_meta().await.expect("Failed to get spot meta")
-        });
+        let metadata = self.metadata();
+        let spot_meta = &metadata.spot;
         
         // Build the index to name map for tokens
         let index_to_name: std::collections::HashMap<usize, String> = spot_meta
@@ -179,11 +529,92 @@ impl HyperliquidClient {
         pairs
     }
     
+    pub fn get_spot_pairs(&self) -> Vec<SpotPair> {
+        let metadata = self.metadata();
+        metadata.spot.universe.iter()
+            .filter_map(|pair| {
+                let base = metadata.spot.token(pair.tokens[0])?;
+                let quote = metadata.spot.token(pair.tokens[1])?;
+                Some(SpotPair {
+                    index: pair.index as u32,
+                    id: format!("@{}", pair.index),
+                    name: format!("{}/{}", base.name, quote.name),
+                    base: base.name.clone(),
+                    quote: quote.name.clone(),
+                    is_canonical: pair.is_canonical,
+                    sz_decimals: base.sz_decimals,
+                })
+            })
+            .collect()
+    }
+    
+    pub fn get_spot_tokens(&self) -> Vec<SpotToken> {
+        self.metadata().spot.tokens.iter()
+            .map(|token| SpotToken {
+                index: token.index as u32,
+                name: token.name.clone(),
+                token_id: token.token_id.clone(),
+                sz_decimals: token.sz_decimals,
+                wei_decimals: token.wei_decimals,
+                full_name: token.full_name.clone(),
+                evm_contract: token.evm_contract.as_ref().map(|contract| contract.address.clone()),
+                evm_extra_wei_decimals: token.evm_contract.as_ref().map(|contract| contract.evm_extra_wei_decimals),
+            })
+            .collect()
+    }
+    
+    /// Supply and deployment details; `token_id` is the 0x-prefixed id from `get_spot_tokens`.
+    /// None for a malformed or unknown id, or when the request fails.
+    pub fn get_token_details(&self, token_id: String) -> Option<TokenDetails> {
+        let hex = token_id.strip_prefix("0x")?;
+        if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        
+        let request_body = serde_json::json!({
+            "type": "tokenDetails",
+            "tokenId": token_id
+        });
+        // Unknown ids are answered with an error status, not an empty object
+        let details = self.runtime.block_on(self.info_call("tokenDetails", || send_info(&self.http, &request_body))).ok()?;
+        if !details.is_object() {
+            return None;
+        }
+        
+        Some(TokenDetails {
+            token_id,
+            name: details["name"].as_str().unwrap_or("").to_string(),
+            max_supply: details["maxSupply"].as_str().unwrap_or("0.0").to_string(),
+            total_supply: details["totalSupply"].as_str().unwrap_or("0.0").to_string(),
+            circulating_supply: details["circulatingSupply"].as_str().unwrap_or("0.0").to_string(),
+            future_emissions: details["futureEmissions"].as_str().unwrap_or("0.0").to_string(),
+            non_circulating_balances: details["nonCirculatingUserBalances"].as_array().cloned().unwrap_or_default().iter()
+                .map(|balance| TokenHolderBalance {
+                    address: balance[0].as_str().unwrap_or("").to_string(),
+                    balance: balance[1].as_str().unwrap_or("0.0").to_string(),
+                })
+                .collect(),
+            sz_decimals: details["szDecimals"].as_u64().unwrap_or(0) as u32,
+            wei_decimals: details["weiDecimals"].as_u64().unwrap_or(0) as u32,
+            mid_price: details["midPx"].as_str().map(|s| s.to_string()),
+            mark_price: details["markPx"].as_str().unwrap_or("0.0").to_string(),
+            prev_day_price: details["prevDayPx"].as_str().unwrap_or("0.0").to_string(),
+            deployer: details["deployer"].as_str().map(|s| s.to_string()),
+            deploy_gas: details["deployGas"].as_str().map(|s| s.to_string()),
+            deploy_time: details["deployTime"].as_str().map(|s| s.to_string()),
+            seeded_usdc: details["seededUsdc"].as_str().unwrap_or("0.0").to_string(),
+        })
+    }
+    
     pub fn get_token_balances(&self, address: String) -> Vec<TokenBalance> {
+        if let Some(paper) = self.paper_account(&address) {
+            return paper.balances();
+        }
+        
         let info = self.info.clone();
         let balances = self.runtime.block_on(async move {
             let addr = address.parse().expect("Invalid address");
-            info.user_token_balances(addr).await.expect("Failed to get balances")
+            self.info_call("spotClearinghouseState", || info.user_token_balances(addr)).await.expect("Failed to get balances")
         });
         
         balances.balances.iter()
@@ -195,13 +626,341 @@ impl HyperliquidClient {
             .collect()
     }
     
+    pub fn get_address(&self) -> Option<String> {
+        self.address.map(|addr| format!("{:#x}", addr))
+    }
+    
+    pub fn is_watch_only(&self) -> bool {
+        self.address.is_some() && self.exchange.is_none() && self.paper.is_none()
+    }
+    
+    pub fn is_paper_trading(&self) -> bool {
+        self.paper.is_some()
+    }
+    
+    /// Feeds a paper-trading client a book for `coin` as of `time`, filling
+    /// resting orders it crosses. False for other clients.
+    pub fn paper_update_book(&self, coin: String, book: OrderbookData, time: u64) -> bool {
+        let Some(paper) = &self.paper else {
+            return false;
+        };
+        let levels = |levels: &[OrderLevel]| {
+            levels.iter()
+                .map(|level| (number(&level.price), number(&level.size)))
+                .collect()
+        };
+        paper.update_book(&coin, levels(&book.bids), levels(&book.asks), time);
+        true
+    }
+    
+    /// Like `paper_update_book`, for a feed of mids: orders fill in full at the mid.
+    pub fn paper_update_mid(&self, coin: String, price: f64, time: u64) -> bool {
+        let Some(paper) = &self.paper else {
+            return false;
+        };
+        paper.update_mid(&coin, price, time);
+        true
+    }
+    
+    pub fn get_balances(&self) -> Result<Vec<TokenBalance>, SignerError> {
+        Ok(self.get_token_balances(self.bound_address()?))
+    }
+    
+    pub fn get_positions(&self) -> Result<Vec<Position>, SignerError> {
+        Ok(self.get_user_positions(self.bound_address()?))
+    }
+    
+    pub fn get_open_orders(&self) -> Result<Vec<OpenOrder>, SignerError> {
+        Ok(self.get_user_open_orders(self.bound_address()?))
+    }
+    
+    pub fn get_fills(&self, start_time: u64, end_time: Option<u64>) -> Result<Vec<UserFill>, SignerError> {
+        Ok(self.get_user_fills_by_time(self.bound_address()?, start_time, end_time))
+    }
+    
+    /// Cost basis and PnL per coin from the address's fills since `start_time`,
+    /// valued at current mids.
+    pub fn get_pnl(&self, address: String, start_time: u64, method: CostBasisMethod) -> Vec<CoinPnl> {
+        let fills = self.get_user_fills_by_time(address, start_time, None);
+        let current_prices = match self.recorded_feed() {
+            Some(paper) => paper.mids().into_iter().collect(),
+            None => {
+                let info = self.info.clone();
+                let mids = self.runtime.block_on(async move {
+                    self.info_call("allMids", || info.all_mids()).await.expect("Failed to get mids")
+                });
+                mids.iter()
+                    .filter_map(|(coin, price)| Some((coin.clone(), price.parse::<f64>().ok()?)))
+                    .collect()
+            }
+        };
+        compute_pnl(fills, current_prices, method)
+    }
+    
+    /// Trades, FIFO tax lots, funding and ledger updates of a UTC calendar year,
+    /// with fees, and lots of pairs quoted in other tokens than USDC, valued in
+    /// USD at fill time from hourly or daily candles.
+    ///
+    /// Lots are matched against all fills the API still serves before the year
+    /// ends, which stops at the 10000 most recent; `incomplete_history` flags
+    /// lots that were open before the oldest of them.
+    pub fn get_tax_report(&self, address: String, year: i32) -> TaxReport {
+        let (start_time, end_time) = year_bounds(year);
+        let fills = self.get_user_fills_by_time(address.clone(), 0, Some(end_time));
+        let (funding, ledger) = self.runtime.block_on(async {
+            let funding_body = serde_json::json!({ "type": "userFunding", "user": address });
+            let ledger_body = serde_json::json!({ "type": "userNonFundingLedgerUpdates", "user": address });
+            (
+                self.post_info_pages(funding_body, start_time, Some(end_time), LEDGER_PAGE_SIZE).await,
+                self.post_info_pages(ledger_body, start_time, Some(end_time), LEDGER_PAGE_SIZE).await,
+            )
+        });
+        let funding = funding.iter().map(parse_funding_payment).collect();
+        let ledger = ledger.iter().map(|update| parse_ledger_entry(update, &address)).collect();
+        
+        // Quote and fee tokens other than USDC are priced from candles fetched
+        // once per token over the fills that need them
+        let metadata = self.metadata();
+        let mut spans: HashMap<&str, (u64, u64)> = HashMap::new();
+        for fill in fills.iter().filter(|fill| fill.time <= end_time) {
+            let (base, quote) = metadata.spot_tokens(&fill.coin).unzip();
+            let fee_token = fill.fee_token.as_deref().filter(|token| Some(*token) != base);
+            for token in [quote, fee_token].into_iter().flatten() {
+                let span = spans.entry(token).or_insert((fill.time, fill.time));
+                *span = (span.0.min(fill.time), span.1.max(fill.time));
+            }
+        }
+        let prices = self.usd_prices(&metadata, spans);
+        
+        // Spot buys pay fees in the base token, priced in the pair's quote
+        build_report(
+            address.clone(),
+            year,
+            fills,
+            funding,
+            ledger,
+            |fill, fee| {
+                let fee_token = fill.fee_token.as_deref().unwrap_or("USDC");
+                match metadata.spot_tokens(&fill.coin) {
+                    Some((base, quote)) if base == fee_token => {
+                        let price = fill.px.parse::<f64>().ok()?;
+                        Some(fee * price * prices.at(quote, fill.time)?)
+                    }
+                    _ => Some(fee * prices.at(fee_token, fill.time)?),
+                }
+            },
+            |coin, time| match metadata.spot_tokens(coin) {
+                Some((_, quote)) => prices.at(quote, time),
+                None => Some(1.0),
+            },
+        )
+    }
+    
+    pub fn get_user_positions(&self, address: String) -> Vec<Position> {
+        if let Some(paper) = self.paper_account(&address) {
+            return paper.positions();
+        }
+        
+        let info = self.info.clone();
+        let state = self.runtime.block_on(async move {
+            let addr = address.parse().expect("Invalid address");
+            self.info_call("clearinghouseState", || info.user_state(addr)).await.expect("Failed to get user state")
+        });
+        
+        state.asset_positions.iter()
+            .map(|asset_position| {
+                let position = &asset_position.position;
+                Position {
+                    coin: position.coin.clone(),
+                    size: position.szi.clone(),
+                    entry_price: position.entry_px.clone(),
+                    position_value: position.position_value.clone(),
+                    unrealized_pnl: position.unrealized_pnl.clone(),
+                    return_on_equity: position.return_on_equity.clone(),
+                    liquidation_price: position.liquidation_px.clone(),
+                    margin_used: position.margin_used.clone(),
+                    leverage: position.leverage.value,
+                    leverage_type: position.leverage.type_string.clone(),
+                }
+            })
+            .collect()
+    }
+    
+    pub fn get_user_open_orders(&self, address: String) -> Vec<OpenOrder> {
+        if let Some(paper) = self.paper_account(&address) {
+            return paper.open_orders();
+        }
+        
+        let info = self.info.clone();
+        let orders = self.runtime.block_on(async move {
+            let addr = address.parse().expect("Invalid address");
+            self.info_call("openOrders", || info.open_orders(addr)).await.expect("Failed to get open orders")
+        });
+        
+        orders.iter()
+            .map(|order| OpenOrder {
+                coin: order.coin.clone(),
+                side: order.side.clone(),
+                limit_price: order.limit_px.clone(),
+                size: order.sz.clone(),
+                order_id: order.oid,
+                timestamp: order.timestamp,
+            })
+            .collect()
+    }
+    
+    pub fn watch_address(&self, address: String) -> Result<bool, SignerError> {
+        let addr = parse_address(&address)?;
+        let mut watched = self.watched.lock().unwrap();
+        if watched.contains(&addr) {
+            return Ok(false);
+        }
+        watched.push(addr);
+        Ok(true)
+    }
+    
+    pub fn unwatch_address(&self, address: String) -> Result<bool, SignerError> {
+        let addr = parse_address(&address)?;
+        let mut watched = self.watched.lock().unwrap();
+        let before = watched.len();
+        watched.retain(|watched_addr| *watched_addr != addr);
+        Ok(watched.len() != before)
+    }
+    
+    pub fn get_watched_addresses(&self) -> Vec<String> {
+        self.watched.lock().unwrap().iter()
+            .map(|addr| format!("{:#x}", addr))
+            .collect()
+    }
+    
+    pub fn get_watched_accounts(&self) -> Vec<WatchedAccount> {
+        self.get_watched_addresses().into_iter()
+            .map(|address| WatchedAccount {
+                balances: self.get_token_balances(address.clone()),
+                positions: self.get_user_positions(address.clone()),
+                open_orders: self.get_user_open_orders(address.clone()),
+                address,
+            })
+            .collect()
+    }
+    
+    pub fn list_mnemonic_accounts(&self, phrase: String, count: u32) -> Result<Vec<MnemonicAccount>, SignerError> {
+        let derived = derive_mnemonic_accounts(phrase, count)?;
+        let info = self.info.clone();
+        self.runtime.block_on(async move {
+            let mut accounts = Vec::new();
+            for account in derived {
+                let addr = parse_address(&account.address)?;
+                let state = self.info_call("clearinghouseState", || info.user_state(addr)).await.map_err(request_failed)?;
+                let balances = self.info_call("spotClearinghouseState", || info.user_token_balances(addr)).await.map_err(request_failed)?;
+                
+                accounts.push(MnemonicAccount {
+                    index: account.index,
+                    derivation_path: account.derivation_path,
+                    address: account.address,
+                    account_value: state.margin_summary.account_value,
+                    balances: balances.balances.iter()
+                        .map(|balance| TokenBalance {
+                            coin: balance.coin.clone(),
+                            total: balance.total.clone(),
+                            hold: balance.hold.clone(),
+                        })
+                        .collect(),
+                });
+            }
+            Ok(accounts)
+        })
+    }
+    
+    /// What `swap_usdc_to_btc` would buy at the current mid, net of the user's taker fee.
+    /// Empty when the amount is invalid or there is no BTC price.
+    pub fn quote_usdc_to_btc(&self, usdc_amount: String) -> SwapQuote {
+        let Ok(usdc_amount_f64) = usdc_amount.parse::<f64>() else {
+            return SwapQuote::default();
+        };
+        let fee_rate = self.spot_taker_rate();
+        let Ok(btc_price) = self.btc_spot_price() else {
+            return SwapQuote::default();
+        };
+        
+        // Recorded feeds work offline, without metadata
+        let btc_size = match self.recorded_feed() {
+            Some(_) => Some(usdc_amount_f64 / btc_price),
+            None => self.runtime.block_on(self.metadata.get()).ok()
+                .and_then(|metadata| metadata.round_size("UBTC/USDC", usdc_amount_f64 / btc_price)),
+        };
+        let Some(btc_size) = btc_size else {
+            return SwapQuote::default();
+        };
+        let fee = btc_size * fee_rate;
+        SwapQuote {
+            price: btc_price.to_string(),
+            size: btc_size.to_string(),
+            fee_rate: fee_rate.to_string(),
+            fee: fee.to_string(),
+            fee_token: "UBTC".to_string(),
+            expected_proceeds: (btc_size - fee).to_string(),
+        }
+    }
+    
+    /// USDC a market sell of `btc_amount` would return at the current mid, net of the user's taker fee.
+    /// Empty when the amount is invalid or there is no BTC price.
+    pub fn quote_btc_to_usdc(&self, btc_amount: String) -> SwapQuote {
+        let Ok(btc_amount_f64) = btc_amount.parse::<f64>() else {
+            return SwapQuote::default();
+        };
+        let fee_rate = self.spot_taker_rate();
+        let Ok(btc_price) = self.btc_spot_price() else {
+            return SwapQuote::default();
+        };
+        
+        let notional = btc_amount_f64 * btc_price;
+        let fee = notional * fee_rate;
+        SwapQuote {
+            price: btc_price.to_string(),
+            size: btc_amount_f64.to_string(),
+            fee_rate: fee_rate.to_string(),
+            fee: fee.to_string(),
+            fee_token: "USDC".to_string(),
+            expected_proceeds: (notional - fee).to_string(),
+        }
+    }
+    
     pub fn swap_usdc_to_btc(&self, usdc_amount: String) -> SwapResult {
+        // Same order as below, with the same 1% slippage, on the paper book
+        if self.paper.is_some() {
+            let Ok(usdc_amount_f64) = usdc_amount.parse::<f64>() else {
+                return SwapResult {
+                    success: false,
+                    message: "Invalid USDC amount format".to_string(),
+                    order_id: None,
+                    filled_size: None,
+                    avg_price: None,
+                };
+            };
+            let btc_price = match self.btc_spot_price() {
+                Ok(price) => price,
+                Err(message) => {
+                    return SwapResult {
+                        success: false,
+                        message,
+                        order_id: None,
+                        filled_size: None,
+                        avg_price: None,
+                    };
+                }
+            };
+            let size = (usdc_amount_f64 / btc_price).to_string();
+            let limit_price = (btc_price * 1.01).to_string();
+            return self.place_limit_order_for(None, "UBTC/USDC".to_string(), true, size, limit_price, "Ioc".to_string());
+        }
+        
         let exchange = match &self.exchange {
             Some(ex) => ex.clone(),
             None => {
                 return SwapResult {
                     success: false,
-                    message: "No wallet configured. Use new_with_wallet() constructor.".to_string(),
+                    message: self.no_wallet_message(),
                     order_id: None,
                     filled_size: None,
                     avg_price: None,
@@ -210,9 +969,11 @@ impl HyperliquidClient {
         };
         
         let info = self.info.clone();
+        let builder = self.builder.lock().unwrap().clone();
+        let fee_rate = self.spot_taker_rate();
         let swap_result = self.runtime.block_on(async move {
             // First get current UBTC price for the spot market
-            let all_mids = info.all_mids().await
+            let all_mids = self.info_call("allMids", || info.all_mids()).await
                 .map_err(|e| format!("Failed to get prices: {}", e))?;
             
             // Try to get UBTC price first, fall back to BTC if not found
@@ -226,10 +987,11 @@ impl HyperliquidClient {
             let usdc_amount_f64: f64 = usdc_amount.parse()
                 .map_err(|_| "Invalid USDC amount format")?;
             
-            // BTC has 5 decimals precision, calculate and round appropriately
-            let btc_size_raw = usdc_amount_f64 / btc_price;
-            // Round to 5 decimal places for BTC
-            let btc_size = (btc_size_raw * 100000.0).round() / 100000.0;
+            // Round to UBTC's size and price precision
+            let metadata = self.metadata.get().await
+                .map_err(|e| format!("Failed to get metadata: {}", e))?;
+            let btc_size = metadata.round_size("UBTC/USDC", usdc_amount_f64 / btc_price)
+                .ok_or("UBTC/USDC not found in spot metadata")?;
             
             // Ensure minimum order size (0.00001 BTC minimum)
             if btc_size < 0.00001 {
@@ -237,16 +999,14 @@ impl HyperliquidClient {
             }
             
             // Create spot buy order for UBTC/USDC spot pair
-            // Round price to reasonable tick size (try $10 for high-value assets)
-            let tick_size = 10.0;
-            let limit_price_raw = btc_price * 1.01; // 1% slippage
-            let limit_price = (limit_price_raw / tick_size).round() * tick_size;
+            let limit_price = metadata.round_price("UBTC/USDC", btc_price * 1.01) // 1% slippage
+                .ok_or("UBTC/USDC not found in spot metadata")?;
             
             let order = ClientOrderRequest {
                 asset: "UBTC/USDC".to_string(),  // Spot BTC trading pair
                 is_buy: true,
                 reduce_only: false,
-                limit_px: limit_price, // Price rounded to tick size with slippage
+                limit_px: limit_price, // Price rounded to valid precision with slippage
                 sz: btc_size,
                 cloid: None,
                 order_type: ClientOrder::Limit(ClientLimit {
@@ -254,7 +1014,7 @@ impl HyperliquidClient {
                 }),
             };
             
-            let response = exchange.order(order, None).await
+            let response = exchange.order(order, None, builder).await
                 .map_err(|e| format!("Failed to place order: {}", e))?;
             
             match response {
@@ -264,14 +1024,18 @@ impl HyperliquidClient {
                             match status {
                                 ExchangeDataStatus::Filled(order) => Ok((
                                     true,
-                                    format!("Order filled successfully"),
+                                    // Spot buy fees are taken out of the bought asset
+                                    format!(
+                                        "Order filled successfully, ~{} UBTC received after fees",
+                                        number(&order.total_sz) * (1.0 - fee_rate)
+                                    ),
                                     Some(order.oid),
                                     Some(order.total_sz.clone()),
                                     Some(order.avg_px.clone()),
                                 )),
                                 ExchangeDataStatus::Resting(order) => Ok((
                                     true,
-                                    format!("Order placed and resting"),
+                                    "Order placed and resting".to_string(),
                                     Some(order.oid),
                                     None,
                                     None,
@@ -308,84 +1072,104 @@ impl HyperliquidClient {
     }
     
     pub fn get_candles_snapshot(&self, coin: String, interval: String, start_time: u64, end_time: u64) -> Vec<CandleData> {
-        let info = self.info.clone();
-        let candles = self.runtime.block_on(async move {
-            info.candles_snapshot(coin, interval, start_time, end_time).await.expect("Failed to get candles")
-        });
+        self.runtime.block_on(self.fetch_candles(&coin, &interval, start_time, end_time))
+    }
+    
+    /// Like `get_candles_snapshot`, with a typed interval and numeric values.
+    pub fn get_candles(&self, coin: String, interval: CandleInterval, start_time: u64, end_time: u64) -> Vec<Candle> {
+        let end_time = end_time.min(chrono::Utc::now().timestamp_millis() as u64);
+        let candles = self.runtime.block_on(self.candles_between(&coin, interval, start_time, end_time));
+        candles.iter().map(|candle| Candle::from_data(candle, interval)).collect()
+    }
+    
+    /// Candles over any range, fetched in chunks the API will not truncate.
+    pub fn get_candles_range(&self, coin: String, interval: CandleInterval, start_time: u64, end_time: u64) -> CandleRange {
+        // Nothing can open after now, so a range reaching into the future is not a gap
+        let end_time = end_time.min(chrono::Utc::now().timestamp_millis() as u64);
+        let candles = self.runtime.block_on(self.candles_between(&coin, interval, start_time, end_time));
         
-        candles.iter()
-            .map(|candle| CandleData {
-                time_open: candle.time_open,
-                time_close: candle.time_close,
-                coin: candle.coin.clone(),
-                interval: candle.candle_interval.clone(),
-                open: candle.open.clone(),
-                close: candle.close.clone(),
-                high: candle.high.clone(),
-                low: candle.low.clone(),
-                volume: candle.vlm.clone(),
-                num_trades: candle.num_trades,
+        let opens: Vec<u64> = candles.iter().map(|candle| candle.time_open).collect();
+        let gaps = find_gaps(interval.step(), &opens, start_time, end_time).into_iter()
+            .map(|(first_missing_open, last_missing_open, missing_candles)| CandleGap {
+                first_missing_open,
+                last_missing_open,
+                missing_candles,
             })
-            .collect()
+            .collect();
+        
+        CandleRange {
+            coin,
+            interval,
+            candles,
+            gaps,
+        }
     }
     
     pub fn get_user_fills_by_time(&self, address: String, start_time: u64, end_time: Option<u64>) -> Vec<UserFill> {
-        let _info = self.info.clone();
-        let fills_result = self.runtime.block_on(async move {
-            let addr: ethers::types::H160 = address.parse().expect("Invalid address");
-            
-            // Make raw API request for user fills by time
-            let request_body = serde_json::json!({
-                "type": "userFillsByTime",
-                "user": format!("{:#x}", addr),
-                "startTime": start_time,
-                "endTime": end_time
-            });
-            
-            // Use the info client's internal request method
-            let client = reqwest::Client::new();
-            let response = client
-                .post("https://api.hyperliquid.xyz/info")
-                .json(&request_body)
-                .send()
-                .await
-                .expect("Failed to send request");
-            
-            let fills: Vec<serde_json::Value> = response
-                .json()
-                .await
-                .expect("Failed to parse response");
-            
-            fills
-        });
+        if let Some(paper) = self.paper_account(&address) {
+            return paper.fills(start_time, end_time);
+        }
         
-        fills_result.iter()
-            .map(|fill| UserFill {
-                coin: fill["coin"].as_str().unwrap_or("").to_string(),
-                px: fill["px"].as_str().unwrap_or("0.0").to_string(),
-                sz: fill["sz"].as_str().unwrap_or("0.0").to_string(),
-                side: fill["side"].as_str().unwrap_or("").to_string(),
-                time: fill["time"].as_u64().unwrap_or(0),
-                start_position: fill["startPosition"].as_str().unwrap_or("0.0").to_string(),
-                dir: fill["dir"].as_str().unwrap_or("").to_string(),
-                closed_pnl: fill["closedPnl"].as_str().unwrap_or("0.0").to_string(),
-                hash: fill["hash"].as_str().unwrap_or("").to_string(),
-                oid: fill["oid"].as_u64().unwrap_or(0),
-                crossed: fill["crossed"].as_bool().unwrap_or(false),
-                fee: fill["fee"].as_str().map(|s| s.to_string()),
-                tid: fill["tid"].as_u64(),
-                fee_token: fill["feeToken"].as_str().map(|s| s.to_string()),
-            })
-            .collect()
+        let addr: H160 = address.parse().expect("Invalid address");
+        let address = format!("{:#x}", addr);
+        let Some(cache) = self.cache() else {
+            return self.runtime.block_on(self.fetch_fills(&address, start_time, end_time));
+        };
+        
+        self.runtime.block_on(async {
+            match self.cached_fills(&cache, &address, start_time, end_time).await {
+                Ok(fills) => fills,
+                Err(e) => {
+                    log::warn!("Fill cache failed, fetching directly: {}", e);
+                    self.fetch_fills(&address, start_time, end_time).await
+                }
+            }
+        })
     }
     
     pub fn place_limit_order(&self, asset: String, is_buy: bool, size: String, price: String, time_in_force: String) -> SwapResult {
-        let exchange = match &self.exchange {
+        self.place_limit_order_for(None, asset, is_buy, size, price, time_in_force)
+    }
+}
+
+impl HyperliquidClient {
+    fn place_limit_order_for(&self, vault_address: Option<H160>, asset: String, is_buy: bool, size: String, price: String, time_in_force: String) -> SwapResult {
+        if let Some(paper) = &self.paper {
+            let result = match vault_address {
+                Some(_) => Err("Paper trading has no sub-accounts or vaults".to_string()),
+                None => self.place_paper_order(paper, &asset, is_buy, &size, &price, &time_in_force),
+            };
+            return match result {
+                Ok(PaperOrderOutcome::Filled { oid, size, average_price }) => SwapResult {
+                    success: true,
+                    message: "Order filled successfully".to_string(),
+                    order_id: Some(oid),
+                    filled_size: Some(decimal(size)),
+                    avg_price: Some(decimal(average_price)),
+                },
+                Ok(PaperOrderOutcome::Resting { oid }) => SwapResult {
+                    success: true,
+                    message: "Order placed and resting in orderbook".to_string(),
+                    order_id: Some(oid),
+                    filled_size: None,
+                    avg_price: None,
+                },
+                Err(e) => SwapResult {
+                    success: false,
+                    message: e,
+                    order_id: None,
+                    filled_size: None,
+                    avg_price: None,
+                },
+            };
+        }
+        
+        let exchange = match &self.exchange {
             Some(ex) => ex.clone(),
             None => {
                 return SwapResult {
                     success: false,
-                    message: "No wallet configured. Use new_with_wallet() constructor.".to_string(),
+                    message: self.no_wallet_message(),
                     order_id: None,
                     filled_size: None,
                     avg_price: None,
@@ -393,6 +1177,7 @@ impl HyperliquidClient {
             }
         };
         
+        let builder = self.builder.lock().unwrap().clone();
         let order_result = self.runtime.block_on(async move {
             let size_f64: f64 = size.parse()
                 .map_err(|_| "Invalid size format")?;
@@ -404,21 +1189,20 @@ impl HyperliquidClient {
                 return Err("Order size must be positive".to_string());
             }
             
-            // For BTC/USDC spot pair, round to appropriate precision
-            let rounded_size = if asset.contains("BTC") || asset.contains("UBTC") {
-                // BTC has 5 decimal places
-                (size_f64 * 100000.0).round() / 100000.0
-            } else {
-                // Most other assets have 6 decimal places  
-                (size_f64 * 1000000.0).round() / 1000000.0
-            };
+            // Round to the precision the exchange accepts for this asset
+            let metadata = self.metadata.get().await
+                .map_err(|e| format!("Failed to get metadata: {}", e))?;
+            let rounded_size = metadata.round_size(&asset, size_f64)
+                .ok_or(format!("Unknown asset: {}", asset))?;
+            let rounded_price = metadata.round_price(&asset, price_f64)
+                .ok_or(format!("Unknown asset: {}", asset))?;
             
-            let rounded_price = if asset.contains("USDC") {
-                // USDC pairs typically use 2-4 decimal places
-                (price_f64 * 100.0).round() / 100.0
-            } else {
-                price_f64
-            };
+            // The builder may be set up to the spot cap, which perps don't accept
+            if let Some(builder) = &builder {
+                if builder.fee > max_builder_fee_tenths_bps(metadata.is_spot(&asset)) as u64 {
+                    return Err(format!("Builder fee of {} tenths of a bp exceeds the 0.1% perp cap", builder.fee));
+                }
+            }
             
             let order = ClientOrderRequest {
                 asset: asset.clone(),
@@ -432,7 +1216,7 @@ impl HyperliquidClient {
                 }),
             };
             
-            let response = exchange.order(order, None).await
+            let response = exchange.order(order, vault_address, builder).await
                 .map_err(|e| format!("Failed to place order: {}", e))?;
             
             match response {
@@ -442,14 +1226,14 @@ impl HyperliquidClient {
                             match status {
                                 ExchangeDataStatus::Filled(order) => Ok((
                                     true,
-                                    format!("Order filled successfully"),
+                                    "Order filled successfully".to_string(),
                                     Some(order.oid),
                                     Some(order.total_sz.clone()),
                                     Some(order.avg_px.clone()),
                                 )),
                                 ExchangeDataStatus::Resting(order) => Ok((
                                     true,
-                                    format!("Order placed and resting in orderbook"),
+                                    "Order placed and resting in orderbook".to_string(),
                                     Some(order.oid),
                                     None,
                                     None,
@@ -484,19 +1268,26 @@ impl HyperliquidClient {
             },
         }
     }
-    
+}
+
+#[uniffi::export]
+impl HyperliquidClient {
     pub fn place_btc_buy_order(&self, usdc_amount: String, limit_price: String) -> SwapResult {
-        let info = self.info.clone();
         let result = self.runtime.block_on(async move {
             let usdc_f64: f64 = usdc_amount.parse()
                 .map_err(|_| "Invalid USDC amount format")?;
             let price_f64: f64 = limit_price.parse()
                 .map_err(|_| "Invalid price format")?;
             
-            // Calculate BTC size from USDC amount and limit price
-            let btc_size_raw = usdc_f64 / price_f64;
-            // Round to 5 decimal places for BTC
-            let btc_size = (btc_size_raw * 100000.0).round() / 100000.0;
+            // Calculate BTC size from USDC amount and limit price; recorded
+            // feeds work offline, without metadata
+            let btc_size = match self.recorded_feed() {
+                Some(_) => usdc_f64 / price_f64,
+                None => self.metadata.get().await
+                    .map_err(|e| format!("Failed to get metadata: {}", e))?
+                    .round_size("UBTC/USDC", usdc_f64 / price_f64)
+                    .ok_or("UBTC/USDC not found in spot metadata")?,
+            };
             
             if btc_size < 0.00001 {
                 return Err(format!("Order size too small: {} BTC", btc_size));
@@ -522,12 +1313,396 @@ impl HyperliquidClient {
     }
     
     pub fn cancel_order(&self, asset: String, order_id: u64) -> SwapResult {
+        self.cancel_order_for(None, asset, order_id)
+    }
+    
+    pub fn get_sub_accounts(&self, master_address: String) -> Vec<SubAccount> {
+        let sub_accounts = self.runtime.block_on(async move {
+            let addr: H160 = master_address.parse().expect("Invalid address");
+            let request_body = serde_json::json!({
+                "type": "subAccounts",
+                "user": format!("{:#x}", addr)
+            });
+            
+            self.post_info(request_body).await
+        });
+        
+        // The API returns null rather than [] for accounts without sub-accounts
+        sub_accounts.as_array().cloned().unwrap_or_default().iter()
+            .map(|sub_account| SubAccount {
+                name: sub_account["name"].as_str().unwrap_or("").to_string(),
+                address: sub_account["subAccountUser"].as_str().unwrap_or("").to_string(),
+                master: sub_account["master"].as_str().unwrap_or("").to_string(),
+                account_value: sub_account["clearinghouseState"]["marginSummary"]["accountValue"].as_str().unwrap_or("0.0").to_string(),
+                withdrawable: sub_account["clearinghouseState"]["withdrawable"].as_str().unwrap_or("0.0").to_string(),
+                balances: sub_account["spotState"]["balances"].as_array().cloned().unwrap_or_default().iter()
+                    .map(|balance| TokenBalance {
+                        coin: balance["coin"].as_str().unwrap_or("").to_string(),
+                        total: balance["total"].as_str().unwrap_or("0.0").to_string(),
+                        hold: balance["hold"].as_str().unwrap_or("0.0").to_string(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+    
+    pub fn create_sub_account(&self, name: String) -> ActionResult {
+        self.send_action(Action::CreateSubAccount(CreateSubAccount { name }), None)
+    }
+    
+    // Positive amounts only; `is_deposit` moves master -> sub-account
+    pub fn transfer_sub_account_usdc(&self, sub_account_address: String, usdc_amount: String, is_deposit: bool) -> ActionResult {
+        let sub_account: H160 = match sub_account_address.parse() {
+            Ok(addr) => addr,
+            Err(_) => return ActionResult::failed("Invalid sub-account address".to_string()),
+        };
+        let usd = match usdc_amount.parse::<f64>() {
+            Ok(amount) if amount > 0.0 => (amount * 1_000_000.0).round() as u64,
+            _ => return ActionResult::failed("Invalid USDC amount format".to_string()),
+        };
+        
+        self.send_action(Action::SubAccountTransfer(SubAccountTransfer {
+            sub_account_user: format!("{:#x}", sub_account),
+            is_deposit,
+            usd,
+        }), None)
+    }
+    
+    pub fn transfer_sub_account_spot(&self, sub_account_address: String, token: String, amount: String, is_deposit: bool) -> ActionResult {
+        let sub_account: H160 = match sub_account_address.parse() {
+            Ok(addr) => addr,
+            Err(_) => return ActionResult::failed("Invalid sub-account address".to_string()),
+        };
+        match amount.parse::<f64>() {
+            Ok(value) if value > 0.0 => {}
+            _ => return ActionResult::failed("Invalid amount format".to_string()),
+        }
+        
+        // Spot transfers identify tokens as "NAME:0x<token id>"
+        let token_wire = self.metadata().spot.tokens.iter()
+            .find(|info| info.name == token)
+            .map(|info| format!("{}:{}", info.name, info.token_id));
+        let token_wire = match token_wire {
+            Some(token_wire) => token_wire,
+            None => return ActionResult::failed(format!("Unknown spot token: {}", token)),
+        };
+        
+        self.send_action(Action::SubAccountSpotTransfer(SubAccountSpotTransfer {
+            sub_account_user: format!("{:#x}", sub_account),
+            is_deposit,
+            token: token_wire,
+            amount,
+        }), None)
+    }
+    
+    // Follower state is included when the client is bound to an address
+    pub fn get_vault_details(&self, vault_address: String) -> VaultDetails {
+        let user = self.address;
+        let details = self.runtime.block_on(async move {
+            let addr: H160 = vault_address.parse().expect("Invalid address");
+            let request_body = serde_json::json!({
+                "type": "vaultDetails",
+                "vaultAddress": format!("{:#x}", addr),
+                "user": user.map(|user| format!("{:#x}", user))
+            });
+            
+            self.post_info(request_body).await
+        });
+        
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        VaultDetails {
+            name: details["name"].as_str().unwrap_or("").to_string(),
+            vault_address: details["vaultAddress"].as_str().unwrap_or("").to_string(),
+            leader: details["leader"].as_str().unwrap_or("").to_string(),
+            description: details["description"].as_str().unwrap_or("").to_string(),
+            apr: details["apr"].as_f64().unwrap_or(0.0),
+            leader_fraction: details["leaderFraction"].as_f64().unwrap_or(0.0),
+            leader_commission: details["leaderCommission"].as_f64().unwrap_or(0.0),
+            max_distributable: details["maxDistributable"].as_f64().unwrap_or(0.0),
+            max_withdrawable: details["maxWithdrawable"].as_f64().unwrap_or(0.0),
+            is_closed: details["isClosed"].as_bool().unwrap_or(false),
+            allow_deposits: details["allowDeposits"].as_bool().unwrap_or(false),
+            portfolio: parse_portfolio(&details["portfolio"]),
+            followers: details["followers"].as_array().cloned().unwrap_or_default().iter()
+                .map(|follower| parse_vault_follower(follower, now))
+                .collect(),
+            follower_state: details.get("followerState")
+                .filter(|state| !state.is_null())
+                .map(|state| parse_vault_follower(state, now)),
+        }
+    }
+    
+    /// Account value and PnL series of an address over the last day, week,
+    /// month and all time, plus the same for its perp account alone.
+    pub fn get_portfolio_history(&self, address: String) -> Vec<PortfolioPeriod> {
+        let portfolio = self.runtime.block_on(async move {
+            let addr: H160 = address.parse().expect("Invalid address");
+            let request_body = serde_json::json!({
+                "type": "portfolio",
+                "user": format!("{:#x}", addr)
+            });
+            
+            self.post_info(request_body).await
+        });
+        
+        parse_portfolio(&portfolio)
+    }
+    
+    /// `compute_performance` of every period of `get_portfolio_history`.
+    pub fn get_portfolio_metrics(&self, address: String, risk_free_rate: f64) -> Vec<PerformanceMetrics> {
+        self.get_portfolio_history(address)
+            .into_iter()
+            .map(|period| compute_performance(period, risk_free_rate))
+            .collect()
+    }
+    
+    pub fn get_user_vault_equities(&self, address: String) -> Vec<UserVaultEquity> {
+        let equities = self.runtime.block_on(async move {
+            let addr: H160 = address.parse().expect("Invalid address");
+            let request_body = serde_json::json!({
+                "type": "userVaultEquities",
+                "user": format!("{:#x}", addr)
+            });
+            
+            self.post_info(request_body).await
+        });
+        
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        equities.as_array().cloned().unwrap_or_default().iter()
+            .map(|equity| {
+                let locked_until = equity["lockedUntilTimestamp"].as_u64();
+                UserVaultEquity {
+                    vault_address: equity["vaultAddress"].as_str().unwrap_or("").to_string(),
+                    equity: equity["equity"].as_str().unwrap_or("0.0").to_string(),
+                    locked_until,
+                    is_locked: locked_until.is_some_and(|until| until > now),
+                }
+            })
+            .collect()
+    }
+    
+    pub fn vault_deposit(&self, vault_address: String, usdc_amount: String) -> ActionResult {
+        self.vault_transfer(vault_address, usdc_amount, true)
+    }
+    
+    // Withdrawals fail on the exchange while the deposit is still locked up
+    pub fn vault_withdraw(&self, vault_address: String, usdc_amount: String) -> ActionResult {
+        self.vault_transfer(vault_address, usdc_amount, false)
+    }
+    
+    // Orders are signed by the master key and routed via the vault address field
+    pub fn place_sub_account_limit_order(&self, sub_account_address: String, asset: String, is_buy: bool, size: String, price: String, time_in_force: String) -> SwapResult {
+        match sub_account_address.parse() {
+            Ok(sub_account) => self.place_limit_order_for(Some(sub_account), asset, is_buy, size, price, time_in_force),
+            Err(_) => SwapResult {
+                success: false,
+                message: "Invalid sub-account address".to_string(),
+                order_id: None,
+                filled_size: None,
+                avg_price: None,
+            },
+        }
+    }
+    
+    pub fn cancel_sub_account_order(&self, sub_account_address: String, asset: String, order_id: u64) -> SwapResult {
+        match sub_account_address.parse() {
+            Ok(sub_account) => self.cancel_order_for(Some(sub_account), asset, order_id),
+            Err(_) => SwapResult {
+                success: false,
+                message: "Invalid sub-account address".to_string(),
+                order_id: Some(order_id),
+                filled_size: None,
+                avg_price: None,
+            },
+        }
+    }
+    
+    /// Attaches a builder fee to every order this client places; `fee_tenths_bps` of 10 is 1 bp (0.01%).
+    /// The user has to approve at least this fee for the builder first. Up to 1% is accepted, but
+    /// perp orders are rejected while the fee is above 0.1% (100).
+    pub fn set_builder(&self, builder_address: String, fee_tenths_bps: u32) -> ActionResult {
+        let addr: H160 = match builder_address.parse() {
+            Ok(addr) => addr,
+            Err(_) => return ActionResult::failed("Invalid builder address".to_string()),
+        };
+        if fee_tenths_bps > MAX_BUILDER_FEE_TENTHS_BPS {
+            return ActionResult::failed("Builder fee cannot exceed 1%".to_string());
+        }
+        
+        *self.builder.lock().unwrap() = Some(BuilderInfo {
+            builder: format!("{:#x}", addr),
+            fee: fee_tenths_bps as u64,
+        });
+        ActionResult {
+            success: true,
+            message: "Builder set".to_string(),
+            data: None,
+        }
+    }
+    
+    /// Fetches perp and spot metadata now instead of waiting for the TTL to expire.
+    pub fn refresh_metadata(&self) -> Result<(), RequestError> {
+        self.runtime.block_on(self.metadata.refresh())?;
+        Ok(())
+    }
+    
+    /// How long perp/spot metadata is reused before it is fetched again; also the background refresh interval.
+    pub fn set_metadata_ttl(&self, seconds: u64) {
+        self.metadata.set_ttl(Duration::from_secs(seconds));
+    }
+    
+    /// Caches candles and fills in the SQLite database at `db_path`, created if
+    /// missing; later queries only fetch what the cache does not have yet.
+    pub fn enable_cache(&self, db_path: String) -> Result<(), CacheError> {
+        let store = Store::open(&db_path)?;
+        *self.cache.lock().unwrap() = Some(Arc::new(store));
+        Ok(())
+    }
+    
+    pub fn disable_cache(&self) {
+        *self.cache.lock().unwrap() = None;
+    }
+    
+    pub fn clear_cache(&self) -> Result<(), CacheError> {
+        match self.cache() {
+            Some(cache) => cache.clear(),
+            None => Ok(()),
+        }
+    }
+    
+    /// Applies to info queries, metadata refreshes included; exchange actions
+    /// are never retried.
+    pub fn set_retry_policy(&self, policy: RetryPolicy) {
+        *self.retry_policy.lock().unwrap() = policy;
+    }
+    
+    pub fn get_retry_policy(&self) -> RetryPolicy {
+        self.retry_policy.lock().unwrap().clone()
+    }
+    
+    pub fn clear_builder(&self) {
+        *self.builder.lock().unwrap() = None;
+    }
+    
+    pub fn get_builder(&self) -> Option<BuilderFee> {
+        self.builder.lock().unwrap().as_ref().map(|builder| BuilderFee {
+            address: builder.builder.clone(),
+            fee_tenths_bps: builder.fee as u32,
+        })
+    }
+    
+    pub fn approve_builder_fee(&self, builder_address: String, max_fee_tenths_bps: u32) -> ActionResult {
+        let builder: H160 = match builder_address.parse() {
+            Ok(addr) => addr,
+            Err(_) => return ActionResult::failed("Invalid builder address".to_string()),
+        };
+        if max_fee_tenths_bps > MAX_BUILDER_FEE_TENTHS_BPS {
+            return ActionResult::failed("Builder fee cannot exceed 1%".to_string());
+        }
+        
+        self.send_action(Action::ApproveBuilderFee(ApproveBuilderFee {
+            signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+            hyperliquid_chain: hyperliquid_chain(self.info.http_client.is_mainnet()),
+            max_fee_rate: builder_fee_rate(max_fee_tenths_bps),
+            builder: format!("{:#x}", builder),
+            nonce: next_nonce(),
+        }), None)
+    }
+    
+    /// Largest fee, in tenths of a bp, the user has approved for the builder; 0 if none.
+    pub fn get_max_builder_fee(&self, user_address: String, builder_address: String) -> u32 {
+        let max_fee = self.runtime.block_on(async move {
+            let user: H160 = user_address.parse().expect("Invalid address");
+            let builder: H160 = builder_address.parse().expect("Invalid address");
+            let request_body = serde_json::json!({
+                "type": "maxBuilderFee",
+                "user": format!("{:#x}", user),
+                "builder": format!("{:#x}", builder)
+            });
+            
+            self.post_info(request_body).await
+        });
+        
+        max_fee.as_u64().unwrap_or(0) as u32
+    }
+    
+    pub fn get_user_fees(&self, address: String) -> UserFees {
+        let addr: H160 = address.parse().expect("Invalid address");
+        let fees = self.user_fees_raw(addr);
+        
+        // VIP tiers are based on 14-day volume, the API returns one entry per day
+        let fourteen_day_volume: f64 = fees["dailyUserVlm"].as_array().cloned().unwrap_or_default().iter()
+            .map(|day| {
+                day["userCross"].as_str().map(number).unwrap_or(0.0)
+                    + day["userAdd"].as_str().map(number).unwrap_or(0.0)
+            })
+            .sum();
+        let volume_tier = fees["feeSchedule"]["tiers"]["vip"].as_array().cloned().unwrap_or_default().iter()
+            .filter(|tier| {
+                tier["ntlCutoff"].as_str().and_then(|v| v.parse::<f64>().ok()).is_some_and(|cutoff| fourteen_day_volume >= cutoff)
+            })
+            .count() as u32;
+        
+        UserFees {
+            maker_rate: fees["userAddRate"].as_str().unwrap_or("0.0").to_string(),
+            taker_rate: fees["userCrossRate"].as_str().unwrap_or("0.0").to_string(),
+            spot_maker_rate: fees["userSpotAddRate"].as_str().unwrap_or("0.0").to_string(),
+            spot_taker_rate: fees["userSpotCrossRate"].as_str().unwrap_or("0.0").to_string(),
+            volume_tier,
+            fourteen_day_volume: fourteen_day_volume.to_string(),
+            staking_discount: fees["activeStakingDiscount"]["discount"].as_str().unwrap_or("0.0").to_string(),
+            referral_discount: fees["activeReferralDiscount"].as_str().unwrap_or("0.0").to_string(),
+        }
+    }
+    
+    pub fn get_user_rate_limit(&self, address: String) -> UserRateLimit {
+        let address_for_budget = address.clone();
+        let rate_limit = self.runtime.block_on(async move {
+            let addr: H160 = address.parse().expect("Invalid address");
+            let request_body = serde_json::json!({
+                "type": "userRateLimit",
+                "user": format!("{:#x}", addr)
+            });
+            
+            self.post_info(request_body).await
+        });
+        
+        let requests_used = rate_limit["nRequestsUsed"].as_u64().unwrap_or(0);
+        let requests_cap = rate_limit["nRequestsCap"].as_u64().unwrap_or(0);
+        if let Ok(addr) = address_for_budget.parse() {
+            limiter().set_address_budget(addr, requests_cap.saturating_sub(requests_used));
+        }
+        UserRateLimit {
+            cumulative_volume: rate_limit["cumVlm"].as_str().unwrap_or("0.0").to_string(),
+            requests_used,
+            requests_cap,
+            requests_remaining: requests_cap.saturating_sub(requests_used),
+        }
+    }
+}
+
+impl HyperliquidClient {
+    fn cancel_order_for(&self, vault_address: Option<H160>, asset: String, order_id: u64) -> SwapResult {
+        if let Some(paper) = &self.paper {
+            let result = match vault_address {
+                Some(_) => Err("Paper trading has no sub-accounts or vaults".to_string()),
+                None => paper.cancel_order(&asset, order_id).map_err(|e| format!("Cancel failed: {}", e)),
+            };
+            return SwapResult {
+                success: result.is_ok(),
+                message: result.map_or_else(|e| e, |_| format!("Order {} cancelled successfully", order_id)),
+                order_id: Some(order_id),
+                filled_size: None,
+                avg_price: None,
+            };
+        }
+        
         let exchange = match &self.exchange {
             Some(ex) => ex.clone(),
             None => {
                 return SwapResult {
                     success: false,
-                    message: "No wallet configured. Use new_with_wallet() constructor.".to_string(),
+                    message: self.no_wallet_message(),
                     order_id: None,
                     filled_size: None,
                     avg_price: None,
@@ -541,11 +1716,11 @@ impl HyperliquidClient {
                 oid: order_id,
             };
             
-            let response = exchange.cancel(cancel_request, None).await
+            let response = exchange.cancel(cancel_request, vault_address).await
                 .map_err(|e| format!("Failed to cancel order: {}", e))?;
             
             match response {
-                ExchangeResponseStatus::Ok(resp) => {
+                ExchangeResponseStatus::Ok(_) => {
                     Ok((true, format!("Order {} cancelled successfully", order_id)))
                 }
                 ExchangeResponseStatus::Err(e) => {
@@ -571,6 +1746,352 @@ impl HyperliquidClient {
             },
         }
     }
+    
+    fn bound_address(&self) -> Result<String, SignerError> {
+        let addr = self.address.ok_or(SignerError::NotBound)?;
+        Ok(format!("{:#x}", addr))
+    }
+    
+    fn no_wallet_message(&self) -> String {
+        if self.paper.is_some() {
+            return "Paper trading clients only simulate orders and cancels.".to_string();
+        }
+        match self.address {
+            Some(addr) => format!("Watch-only client for {:#x} cannot sign. Use new_with_wallet() or new_with_signer() to trade.", addr),
+            None => "No wallet configured. Use new_with_wallet() constructor.".to_string(),
+        }
+    }
+    
+    // The simulator, when `address` is the paper account; on a live feed its
+    // books are refreshed first so resting orders and marks are current
+    fn paper_account(&self, address: &str) -> Option<Arc<PaperExchange>> {
+        let paper = self.paper.clone()?;
+        let addr: H160 = address.parse().ok()?;
+        if Some(addr) != self.address {
+            return None;
+        }
+        if paper.feed == PaperFeed::Live {
+            for coin in paper.active_coins() {
+                // The last book seen is still a usable approximation
+                if let Err(e) = self.sync_paper_book(&paper, &coin) {
+                    log::warn!("Paper book sync failed for {}: {}", coin, e);
+                }
+            }
+        }
+        Some(paper)
+    }
+    
+    fn recorded_feed(&self) -> Option<&Arc<PaperExchange>> {
+        self.paper.as_ref().filter(|paper| paper.feed == PaperFeed::Recorded)
+    }
+    
+    fn sync_paper_book(&self, paper: &PaperExchange, coin: &str) -> Result<(), String> {
+        let info = self.info.clone();
+        let l2_data = self.runtime.block_on(async move {
+            // l2Book knows non-canonical spot pairs only by their "@N" name
+            let metadata = self.metadata.get().await
+                .map_err(|e| format!("Failed to get metadata: {}", e))?;
+            let book_coin = metadata.info_coin(coin).to_string();
+            self.info_call("l2Book", || info.l2_snapshot(book_coin.clone())).await
+                .map_err(|e| format!("Failed to get L2 snapshot: {}", e))
+        })?;
+        
+        let levels = |side: usize| {
+            l2_data.levels[side].iter()
+                .map(|level| (number(&level.px), number(&level.sz)))
+                .collect()
+        };
+        paper.update_book(coin, levels(0), levels(1), chrono::Utc::now().timestamp_millis() as u64);
+        Ok(())
+    }
+    
+    fn place_paper_order(&self, paper: &PaperExchange, asset: &str, is_buy: bool, size: &str, price: &str, time_in_force: &str) -> Result<PaperOrderOutcome, String> {
+        let size_f64: f64 = size.parse().map_err(|_| "Invalid size format")?;
+        let price_f64: f64 = price.parse().map_err(|_| "Invalid price format")?;
+        
+        // "@N" pairs need metadata to find their tokens; other spot pairs are named after them
+        let market = match asset.split_once('/') {
+            Some((base, quote)) => PaperMarket::Spot { base: base.to_string(), quote: quote.to_string() },
+            None if asset.starts_with('@') => {
+                let metadata = self.runtime.block_on(self.metadata.get())
+                    .map_err(|e| format!("Failed to get metadata: {}", e))?;
+                let (base, quote) = metadata.spot_tokens(asset).ok_or(format!("Unknown asset: {}", asset))?;
+                PaperMarket::Spot { base: base.to_string(), quote: quote.to_string() }
+            }
+            None => PaperMarket::Perp,
+        };
+        
+        // Live orders are rounded and validated as the exchange would; recorded
+        // feeds work offline, without metadata
+        let (size_f64, price_f64) = match paper.feed {
+            PaperFeed::Live => {
+                let metadata = self.runtime.block_on(self.metadata.get())
+                    .map_err(|e| format!("Failed to get metadata: {}", e))?;
+                let rounded_size = metadata.round_size(asset, size_f64).ok_or(format!("Unknown asset: {}", asset))?;
+                let rounded_price = metadata.round_price(asset, price_f64).ok_or(format!("Unknown asset: {}", asset))?;
+                self.sync_paper_book(paper, asset)?;
+                (rounded_size, rounded_price)
+            }
+            PaperFeed::Recorded => (size_f64, price_f64),
+        };
+        paper.place_order(asset, market, is_buy, size_f64, price_f64, time_in_force)
+    }
+    
+    fn metadata(&self) -> Arc<Metadata> {
+        self.runtime.block_on(self.metadata.get()).expect("Failed to get metadata")
+    }
+    
+    async fn info_call<T, F, Fut>(&self, request_type: &str, request: F) -> Result<T, hyperliquid_rust_sdk::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, hyperliquid_rust_sdk::Error>>,
+    {
+        let policy = self.retry_policy.lock().unwrap().clone();
+        with_retry(&policy, info_weight(request_type), request).await
+    }
+    
+    async fn post_info(&self, request_body: serde_json::Value) -> serde_json::Value {
+        let request_type = request_body["type"].as_str().unwrap_or("").to_string();
+        let response = self.info_call(&request_type, || send_info(&self.http, &request_body))
+            .await
+            .expect("Failed to send request");
+        limiter().charge(response_weight(&request_type, response.as_array().map_or(0, |items| items.len())));
+        response
+    }
+    
+    fn cache(&self) -> Option<Arc<Store>> {
+        self.cache.lock().unwrap().clone()
+    }
+    
+    // Served from the cache when enabled, fetching only ranges not cached yet
+    async fn candles_between(&self, coin: &str, interval: CandleInterval, start_time: u64, end_time: u64) -> Vec<CandleData> {
+        let Some(cache) = self.cache() else {
+            return self.fetch_candle_chunks(coin, interval, start_time, end_time).await;
+        };
+        
+        match self.cached_candles(&cache, coin, interval, start_time, end_time).await {
+            Ok(candles) => candles,
+            Err(e) => {
+                log::warn!("Candle cache failed, fetching directly: {}", e);
+                self.fetch_candle_chunks(coin, interval, start_time, end_time).await
+            }
+        }
+    }
+    
+    async fn cached_candles(&self, cache: &Store, coin: &str, interval: CandleInterval, start_time: u64, end_time: u64) -> Result<Vec<CandleData>, CacheError> {
+        let key = format!("{}:{}", coin, interval.as_str());
+        // The candle still open keeps changing, so it is fetched again next time
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let settled_until = interval.step().open_of(now).saturating_sub(1);
+        for (missing_start, missing_end) in cache.missing_ranges("candles", &key, start_time, end_time)? {
+            // Settled ranges are recorded even when empty, e.g. before the coin was listed
+            let candles = self.fetch_candle_chunks(coin, interval, missing_start, missing_end).await;
+            cache.put_candles(coin, interval.as_str(), &candles)?;
+            cache.add_settled_range("candles", &key, missing_start, missing_end, settled_until)?;
+        }
+        cache.candles(coin, interval.as_str(), start_time, end_time)
+    }
+    
+    async fn fetch_candle_chunks(&self, coin: &str, interval: CandleInterval, start_time: u64, end_time: u64) -> Vec<CandleData> {
+        // Keyed by open time, so candles on chunk boundaries appear once
+        let mut by_open = std::collections::BTreeMap::new();
+        for (chunk_start, chunk_end) in candle_chunks(interval.step(), start_time, end_time) {
+            for candle in self.fetch_candles(coin, interval.as_str(), chunk_start, chunk_end).await {
+                by_open.insert(candle.time_open, candle);
+            }
+        }
+        by_open.into_values().collect()
+    }
+    
+    async fn fetch_candles(&self, coin: &str, interval: &str, start_time: u64, end_time: u64) -> Vec<CandleData> {
+        let info = self.info.clone();
+        let candles = self.info_call("candleSnapshot", || info.candles_snapshot(coin.to_string(), interval.to_string(), start_time, end_time))
+            .await
+            .expect("Failed to get candles");
+        limiter().charge(response_weight("candleSnapshot", candles.len()));
+        
+        candles.iter()
+            .map(|candle| CandleData {
+                time_open: candle.time_open,
+                time_close: candle.time_close,
+                coin: candle.coin.clone(),
+                interval: candle.candle_interval.clone(),
+                open: candle.open.clone(),
+                close: candle.close.clone(),
+                high: candle.high.clone(),
+                low: candle.low.clone(),
+                volume: candle.vlm.clone(),
+                num_trades: candle.num_trades,
+            })
+            .collect()
+    }
+    
+    // Pages are oldest first, so continue after the last entry until a short page
+    async fn post_info_pages(&self, request_body: serde_json::Value, start_time: u64, end_time: Option<u64>, page_size: usize) -> Vec<serde_json::Value> {
+        let mut entries: Vec<serde_json::Value> = Vec::new();
+        let mut page_start = start_time;
+        
+        loop {
+            let mut page_body = request_body.clone();
+            page_body["startTime"] = serde_json::json!(page_start);
+            page_body["endTime"] = serde_json::json!(end_time);
+            let page = self.post_info(page_body).await.as_array().cloned().unwrap_or_default();
+            let page_len = page.len();
+            let last_time = page.last().and_then(|entry| entry["time"].as_u64());
+            entries.extend(page);
+            
+            match last_time {
+                Some(time) if page_len >= page_size && end_time.is_none_or(|end| time < end) => {
+                    page_start = time + 1;
+                }
+                _ => break,
+            }
+        }
+        entries
+    }
+    
+    // Close of the token's USDC pair over the minute containing `time`
+    // Hourly and daily closes of each token's USDC pair over its (first, last) time
+    fn usd_prices(&self, metadata: &Metadata, spans: HashMap<&str, (u64, u64)>) -> UsdPrices {
+        let mut closes = HashMap::new();
+        for (token, (first, last)) in spans {
+            let Some(coin) = metadata.spot_pair(token, "USDC") else {
+                continue;
+            };
+            for interval in USD_PRICE_INTERVALS {
+                let candles = self.runtime.block_on(self.candles_between(coin, interval, candle_open_time(interval, first), last));
+                let by_open = candles.iter()
+                    .filter_map(|candle| Some((candle.time_open, candle.close.parse().ok()?)))
+                    .collect();
+                closes.insert((token.to_string(), interval.as_str()), by_open);
+            }
+        }
+        UsdPrices { closes }
+    }
+    
+    async fn fetch_fills(&self, address: &str, start_time: u64, end_time: Option<u64>) -> Vec<UserFill> {
+        let mut fills: Vec<UserFill> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        let mut page_start = start_time;
+        
+        // Pages are oldest first. Fills of one order often share a timestamp, so the
+        // next page starts at the last fill's time and repeats are skipped
+        loop {
+            let request_body = serde_json::json!({
+                "type": "userFillsByTime",
+                "user": address,
+                "startTime": page_start,
+                "endTime": end_time
+            });
+            let page = self.post_info(request_body).await.as_array().cloned().unwrap_or_default();
+            let page_len = page.len();
+            let before = fills.len();
+            for fill in page.iter().map(parse_user_fill) {
+                if seen.insert(fill_key(&fill)) {
+                    fills.push(fill);
+                }
+            }
+            
+            match fills.last() {
+                Some(last) if page_len >= FILLS_PAGE_SIZE && fills.len() > before => page_start = last.time,
+                _ => break,
+            }
+        }
+        fills
+    }
+    
+    async fn cached_fills(&self, cache: &Store, address: &str, start_time: u64, end_time: Option<u64>) -> Result<Vec<UserFill>, CacheError> {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let end_time = end_time.unwrap_or(now).min(now);
+        for (missing_start, missing_end) in cache.missing_ranges("fills", address, start_time, end_time)? {
+            let fills = self.fetch_fills(address, missing_start, Some(missing_end)).await;
+            cache.put_fills(address, &fills)?;
+            cache.add_settled_range("fills", address, missing_start, missing_end, now.saturating_sub(FILL_SETTLE_MS))?;
+        }
+        cache.fills(address, start_time, end_time)
+    }
+    
+    fn user_fees_raw(&self, address: H160) -> serde_json::Value {
+        self.runtime.block_on(async move {
+            let request_body = serde_json::json!({
+                "type": "userFees",
+                "user": format!("{:#x}", address)
+            });
+            
+            self.post_info(request_body).await
+        })
+    }
+    
+    // Unbound clients get the base schedule, which is what the zero address pays
+    fn spot_taker_rate(&self) -> f64 {
+        if let Some(paper) = &self.paper {
+            return paper.taker_fee_rate;
+        }
+        let fees = self.user_fees_raw(self.address.unwrap_or_else(H160::zero));
+        fees["userSpotCrossRate"].as_str().map(number).unwrap_or(0.0)
+    }
+    
+    fn btc_spot_price(&self) -> Result<f64, String> {
+        if let Some(paper) = self.recorded_feed() {
+            return paper.mid("UBTC/USDC")
+                .or_else(|| paper.mid("UBTC"))
+                .or_else(|| paper.mid("BTC"))
+                .ok_or_else(|| "UBTC/BTC price not found".to_string());
+        }
+        
+        let info = self.info.clone();
+        let all_mids = self.runtime.block_on(async move {
+            self.info_call("allMids", || info.all_mids()).await
+        }).map_err(|e| format!("Failed to get prices: {}", e))?;
+        
+        all_mids.get("UBTC/USDC")
+            .or_else(|| all_mids.get("UBTC"))
+            .or_else(|| all_mids.get("BTC"))
+            .ok_or("UBTC/BTC price not found")?
+            .parse()
+            .map_err(|_| "Invalid BTC price format".to_string())
+    }
+    
+    fn send_action(&self, action: Action, vault_address: Option<H160>) -> ActionResult {
+        let exchange = match &self.exchange {
+            Some(ex) => ex.clone(),
+            None => return ActionResult::failed(self.no_wallet_message()),
+        };
+        
+        let response = self.runtime.block_on(async move {
+            exchange.post_l1_action_raw(action, vault_address).await
+        });
+        
+        match response {
+            Ok(response) if response["status"] == "ok" => ActionResult {
+                success: true,
+                message: "Action accepted".to_string(),
+                data: response["response"]["data"].as_str().map(|s| s.to_string()),
+            },
+            Ok(response) => ActionResult::failed(format!(
+                "Exchange error: {}",
+                response["response"].as_str().unwrap_or(&response.to_string())
+            )),
+            Err(e) => ActionResult::failed(format!("Failed to send action: {}", e)),
+        }
+    }
+    
+    fn vault_transfer(&self, vault_address: String, usdc_amount: String, is_deposit: bool) -> ActionResult {
+        let vault: H160 = match vault_address.parse() {
+            Ok(addr) => addr,
+            Err(_) => return ActionResult::failed("Invalid vault address".to_string()),
+        };
+        let usd = match usdc_amount.parse::<f64>() {
+            Ok(amount) if amount > 0.0 => (amount * 1_000_000.0).round() as u64,
+            _ => return ActionResult::failed("Invalid USDC amount format".to_string()),
+        };
+        
+        self.send_action(Action::VaultTransfer(VaultTransfer {
+            vault_address: format!("{:#x}", vault),
+            is_deposit,
+            usd,
+        }), None)
+    }
 }
 
 #[derive(uniffi::Record)]
@@ -585,6 +2106,107 @@ pub struct AssetInfo {
     pub sz_decimals: i32,
 }
 
+// Numbers are f64 so market lists can sort on them directly
+#[derive(uniffi::Record)]
+pub struct AssetContext {
+    // Name used by the API: perp name, or "@N"/"PURR/USDC" for spot
+    pub coin: String,
+    // "BASE/QUOTE" for spot pairs
+    pub display_name: String,
+    pub is_spot: bool,
+    pub mark_price: f64,
+    pub mid_price: Option<f64>,
+    pub oracle_price: Option<f64>,
+    pub prev_day_price: f64,
+    pub change_24h_percent: f64,
+    // Notional, in USDC
+    pub volume_24h: f64,
+    // Perps only: open interest in coins, hourly funding rate and premium
+    pub open_interest: Option<f64>,
+    pub funding_rate: Option<f64>,
+    pub premium: Option<f64>,
+}
+
+// Rates are fractions per funding interval, 0.0000125 = 0.00125%
+#[derive(uniffi::Record)]
+pub struct FundingRate {
+    pub coin: String,
+    pub funding_rate: f64,
+    pub premium: f64,
+    pub time: u64,
+}
+
+#[derive(uniffi::Record)]
+pub struct PredictedFunding {
+    pub coin: String,
+    pub venues: Vec<VenueFunding>,
+}
+
+#[derive(uniffi::Record)]
+pub struct VenueFunding {
+    // "HlPerp" for Hyperliquid, "BinPerp", "BybitPerp" for other exchanges
+    pub venue: String,
+    pub funding_rate: f64,
+    pub next_funding_time: u64,
+    pub funding_interval_hours: u32,
+    // funding_rate over a year, so venues with different intervals compare
+    pub annualized_rate: f64,
+}
+
+#[derive(uniffi::Record)]
+pub struct SpotPair {
+    pub index: u32,
+    // "@N", the coin name candles, mids and fills use for non-canonical pairs
+    pub id: String,
+    pub name: String,
+    pub base: String,
+    pub quote: String,
+    pub is_canonical: bool,
+    // Of the base token, which sizes are quoted in
+    pub sz_decimals: u32,
+}
+
+#[derive(uniffi::Record)]
+pub struct SpotToken {
+    pub index: u32,
+    pub name: String,
+    pub token_id: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+    pub full_name: Option<String>,
+    // Linked HyperEVM contract address
+    pub evm_contract: Option<String>,
+    // EVM decimals minus wei_decimals
+    pub evm_extra_wei_decimals: Option<i32>,
+}
+
+#[derive(uniffi::Record)]
+pub struct TokenDetails {
+    pub token_id: String,
+    pub name: String,
+    pub max_supply: String,
+    pub total_supply: String,
+    pub circulating_supply: String,
+    pub future_emissions: String,
+    pub non_circulating_balances: Vec<TokenHolderBalance>,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+    pub mid_price: Option<String>,
+    pub mark_price: String,
+    pub prev_day_price: String,
+    pub deployer: Option<String>,
+    pub deploy_gas: Option<String>,
+    // ISO 8601, as returned by the API
+    pub deploy_time: Option<String>,
+    pub seeded_usdc: String,
+}
+
+#[derive(uniffi::Record)]
+pub struct TokenHolderBalance {
+    pub address: String,
+    pub balance: String,
+}
+
 #[derive(uniffi::Record)]
 pub struct PriceInfo {
     pub coin: String,
@@ -610,6 +2232,47 @@ pub struct TokenBalance {
     pub hold: String,
 }
 
+#[derive(uniffi::Record)]
+pub struct Position {
+    pub coin: String,
+    pub size: String,
+    pub entry_price: Option<String>,
+    pub position_value: String,
+    pub unrealized_pnl: String,
+    pub return_on_equity: String,
+    pub liquidation_price: Option<String>,
+    pub margin_used: String,
+    pub leverage: u32,
+    pub leverage_type: String,
+}
+
+#[derive(uniffi::Record)]
+pub struct OpenOrder {
+    pub coin: String,
+    pub side: String,
+    pub limit_price: String,
+    pub size: String,
+    pub order_id: u64,
+    pub timestamp: u64,
+}
+
+#[derive(uniffi::Record)]
+pub struct WatchedAccount {
+    pub address: String,
+    pub balances: Vec<TokenBalance>,
+    pub positions: Vec<Position>,
+    pub open_orders: Vec<OpenOrder>,
+}
+
+#[derive(uniffi::Record)]
+pub struct MnemonicAccount {
+    pub index: u32,
+    pub derivation_path: String,
+    pub address: String,
+    pub account_value: String,
+    pub balances: Vec<TokenBalance>,
+}
+
 #[derive(uniffi::Record)]
 pub struct SwapResult {
     pub success: bool,
@@ -620,6 +2283,126 @@ pub struct SwapResult {
 }
 
 #[derive(uniffi::Record)]
+pub struct ActionResult {
+    pub success: bool,
+    pub message: String,
+    pub data: Option<String>,
+}
+
+impl ActionResult {
+    fn failed(message: String) -> Self {
+        Self {
+            success: false,
+            message,
+            data: None,
+        }
+    }
+}
+
+#[derive(uniffi::Record, Default)]
+pub struct SwapQuote {
+    pub price: String,
+    pub size: String,
+    pub fee_rate: String,
+    pub fee: String,
+    pub fee_token: String,
+    // In `fee_token`, the asset received
+    pub expected_proceeds: String,
+}
+
+// Rates are fractions, 0.00035 = 0.035%
+#[derive(uniffi::Record)]
+pub struct UserFees {
+    pub maker_rate: String,
+    pub taker_rate: String,
+    pub spot_maker_rate: String,
+    pub spot_taker_rate: String,
+    // 0 is the base tier
+    pub volume_tier: u32,
+    pub fourteen_day_volume: String,
+    pub staking_discount: String,
+    pub referral_discount: String,
+}
+
+#[derive(uniffi::Record)]
+pub struct UserRateLimit {
+    pub cumulative_volume: String,
+    pub requests_used: u64,
+    pub requests_cap: u64,
+    pub requests_remaining: u64,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct BuilderFee {
+    pub address: String,
+    // Tenths of a basis point, 10 = 0.01%
+    pub fee_tenths_bps: u32,
+}
+
+#[derive(uniffi::Record)]
+pub struct SubAccount {
+    pub name: String,
+    pub address: String,
+    pub master: String,
+    pub account_value: String,
+    pub withdrawable: String,
+    pub balances: Vec<TokenBalance>,
+}
+
+#[derive(uniffi::Record)]
+pub struct VaultDetails {
+    pub name: String,
+    pub vault_address: String,
+    pub leader: String,
+    pub description: String,
+    pub apr: f64,
+    pub leader_fraction: f64,
+    pub leader_commission: f64,
+    pub max_distributable: f64,
+    pub max_withdrawable: f64,
+    pub is_closed: bool,
+    pub allow_deposits: bool,
+    pub portfolio: Vec<PortfolioPeriod>,
+    pub followers: Vec<VaultFollower>,
+    pub follower_state: Option<VaultFollower>,
+}
+
+#[derive(uniffi::Record, Clone)]
+pub struct PortfolioPeriod {
+    // "day", "week", "month", "allTime", and "perp"-prefixed variants
+    pub period: String,
+    pub account_value_history: Vec<HistoryPoint>,
+    pub pnl_history: Vec<HistoryPoint>,
+    pub volume: String,
+}
+
+#[derive(uniffi::Record, Clone)]
+pub struct HistoryPoint {
+    pub time: u64,
+    pub value: String,
+}
+
+#[derive(uniffi::Record)]
+pub struct VaultFollower {
+    pub user: String,
+    pub vault_equity: String,
+    pub pnl: String,
+    pub all_time_pnl: String,
+    pub days_following: u64,
+    pub vault_entry_time: u64,
+    pub lockup_until: u64,
+    pub is_locked: bool,
+}
+
+#[derive(uniffi::Record)]
+pub struct UserVaultEquity {
+    pub vault_address: String,
+    pub equity: String,
+    pub locked_until: Option<u64>,
+    pub is_locked: bool,
+}
+
+#[derive(uniffi::Record, Clone)]
 pub struct CandleData {
     pub time_open: u64,
     pub time_close: u64,
@@ -633,7 +2416,74 @@ pub struct CandleData {
     pub num_trades: u64,
 }
 
+// CandleData with parsed numbers, for charts
 #[derive(uniffi::Record)]
+pub struct Candle {
+    pub time_open: u64,
+    pub time_close: u64,
+    pub coin: String,
+    pub interval: CandleInterval,
+    pub open: f64,
+    pub close: f64,
+    pub high: f64,
+    pub low: f64,
+    pub volume: f64,
+    pub num_trades: u64,
+}
+
+impl Candle {
+    fn from_data(candle: &CandleData, interval: CandleInterval) -> Self {
+        Self {
+            time_open: candle.time_open,
+            time_close: candle.time_close,
+            coin: candle.coin.clone(),
+            interval,
+            open: number(&candle.open),
+            close: number(&candle.close),
+            high: number(&candle.high),
+            low: number(&candle.low),
+            volume: number(&candle.volume),
+            num_trades: candle.num_trades,
+        }
+    }
+}
+
+// Closes of tokens' USDC pairs by (token, interval) and candle open
+struct UsdPrices {
+    closes: HashMap<(String, &'static str), HashMap<u64, f64>>,
+}
+
+impl UsdPrices {
+    // From the finest candle covering `time`; USDC is always a dollar
+    fn at(&self, token: &str, time: u64) -> Option<f64> {
+        if token == "USDC" {
+            return Some(1.0);
+        }
+        USD_PRICE_INTERVALS.into_iter().find_map(|interval| {
+            self.closes.get(&(token.to_string(), interval.as_str()))?.get(&candle_open_time(interval, time)).copied()
+        })
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct CandleRange {
+    pub coin: String,
+    pub interval: CandleInterval,
+    // Sorted by time_open, one candle per open time
+    pub candles: Vec<CandleData>,
+    pub gaps: Vec<CandleGap>,
+}
+
+// Candles missing inside the requested range, e.g. exchange downtime or
+// before the asset was listed
+#[derive(uniffi::Record)]
+pub struct CandleGap {
+    pub first_missing_open: u64,
+    pub last_missing_open: u64,
+    pub missing_candles: u64,
+}
+
+#[derive(uniffi::Record, Clone)]
 pub struct UserFill {
     pub coin: String,
     pub px: String,
@@ -651,16 +2501,180 @@ pub struct UserFill {
     pub fee_token: Option<String>,
 }
 
+// Asset context numbers are decimal strings; spot contexts lack the perp-only fields
+fn parse_asset_context(coin: String, display_name: String, is_spot: bool, ctx: &serde_json::Value) -> AssetContext {
+    let field = |name: &str| ctx[name].as_str().and_then(|value| value.parse::<f64>().ok());
+    let mark_price = field("markPx").unwrap_or(0.0);
+    let prev_day_price = field("prevDayPx").unwrap_or(0.0);
+    let change_24h_percent = if prev_day_price > 0.0 {
+        (mark_price - prev_day_price) / prev_day_price * 100.0
+    } else {
+        0.0
+    };
+    
+    AssetContext {
+        coin,
+        display_name,
+        is_spot,
+        mark_price,
+        mid_price: field("midPx"),
+        oracle_price: field("oraclePx"),
+        prev_day_price,
+        change_24h_percent,
+        volume_24h: field("dayNtlVlm").unwrap_or(0.0),
+        open_interest: field("openInterest"),
+        funding_rate: field("funding"),
+        premium: field("premium"),
+    }
+}
+
+// Venues without a prediction for the coin are null
+fn parse_venue_funding(venue: &serde_json::Value, prediction: &serde_json::Value) -> Option<VenueFunding> {
+    let funding_rate = prediction["fundingRate"].as_str()?.parse::<f64>().ok()?;
+    // Hyperliquid funds hourly, the others default to every 8 hours
+    let venue = venue.as_str().unwrap_or("").to_string();
+    let default_interval = if venue == "HlPerp" { 1 } else { 8 };
+    let funding_interval_hours = prediction["fundingIntervalHours"].as_u64().unwrap_or(default_interval) as u32;
+    
+    Some(VenueFunding {
+        annualized_rate: annualize_funding_rate(funding_rate, funding_interval_hours),
+        venue,
+        funding_rate,
+        next_funding_time: prediction["nextFundingTime"].as_u64().unwrap_or(0),
+        funding_interval_hours,
+    })
+}
+
+fn parse_user_fill(fill: &serde_json::Value) -> UserFill {
+    UserFill {
+        coin: fill["coin"].as_str().unwrap_or("").to_string(),
+        px: fill["px"].as_str().unwrap_or("0.0").to_string(),
+        sz: fill["sz"].as_str().unwrap_or("0.0").to_string(),
+        side: fill["side"].as_str().unwrap_or("").to_string(),
+        time: fill["time"].as_u64().unwrap_or(0),
+        start_position: fill["startPosition"].as_str().unwrap_or("0.0").to_string(),
+        dir: fill["dir"].as_str().unwrap_or("").to_string(),
+        closed_pnl: fill["closedPnl"].as_str().unwrap_or("0.0").to_string(),
+        hash: fill["hash"].as_str().unwrap_or("").to_string(),
+        oid: fill["oid"].as_u64().unwrap_or(0),
+        crossed: fill["crossed"].as_bool().unwrap_or(false),
+        fee: fill["fee"].as_str().map(|s| s.to_string()),
+        tid: fill["tid"].as_u64(),
+        fee_token: fill["feeToken"].as_str().map(|s| s.to_string()),
+    }
+}
+
+// Portfolio is a list of [period, {accountValueHistory, pnlHistory, vlm}] pairs
+fn parse_portfolio(portfolio: &serde_json::Value) -> Vec<PortfolioPeriod> {
+    portfolio.as_array().cloned().unwrap_or_default().iter()
+        .map(|entry| PortfolioPeriod {
+            period: entry[0].as_str().unwrap_or("").to_string(),
+            account_value_history: parse_history(&entry[1]["accountValueHistory"]),
+            pnl_history: parse_history(&entry[1]["pnlHistory"]),
+            volume: entry[1]["vlm"].as_str().unwrap_or("0.0").to_string(),
+        })
+        .collect()
+}
+
+// History series are [[timestamp, "value"], ...]
+fn parse_history(history: &serde_json::Value) -> Vec<HistoryPoint> {
+    history.as_array().cloned().unwrap_or_default().iter()
+        .map(|point| HistoryPoint {
+            time: point[0].as_u64().unwrap_or(0),
+            value: point[1].as_str().unwrap_or("0.0").to_string(),
+        })
+        .collect()
+}
+
+fn parse_vault_follower(follower: &serde_json::Value, now: u64) -> VaultFollower {
+    let lockup_until = follower["lockupUntil"].as_u64().unwrap_or(0);
+    VaultFollower {
+        user: follower["user"].as_str().unwrap_or("").to_string(),
+        vault_equity: follower["vaultEquity"].as_str().unwrap_or("0.0").to_string(),
+        pnl: follower["pnl"].as_str().unwrap_or("0.0").to_string(),
+        all_time_pnl: follower["allTimePnl"].as_str().unwrap_or("0.0").to_string(),
+        days_following: follower["daysFollowing"].as_u64().unwrap_or(0),
+        vault_entry_time: follower["vaultEntryTime"].as_u64().unwrap_or(0),
+        lockup_until,
+        is_locked: lockup_until > now,
+    }
+}
+
+// Decimal strings from the API, 0 when missing or malformed
+fn number(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+fn parse_address(address: &str) -> Result<H160, SignerError> {
+    address.parse().map_err(|e| SignerError::InvalidAddress {
+        reason: format!("{}: {}", address, e),
+    })
+}
+
+fn request_failed(error: hyperliquid_rust_sdk::Error) -> SignerError {
+    SignerError::RequestFailed { reason: error.to_string() }
+}
+
+// Errors are mapped like the SDK's own, so the retry policy treats both alike
+async fn send_info(http: &reqwest::Client, request_body: &serde_json::Value) -> Result<serde_json::Value, hyperliquid_rust_sdk::Error> {
+    let response = http
+        .post(format!("{}/info", MAINNET_API_URL))
+        .json(request_body)
+        .send()
+        .await
+        .map_err(|e| hyperliquid_rust_sdk::Error::GenericRequest(e.to_string()))?;
+    
+    let status_code = response.status().as_u16();
+    let text = response.text().await
+        .map_err(|e| hyperliquid_rust_sdk::Error::GenericRequest(e.to_string()))?;
+    match status_code {
+        0..=399 => serde_json::from_str(&text).map_err(|e| hyperliquid_rust_sdk::Error::JsonParse(e.to_string())),
+        400..=499 => Err(hyperliquid_rust_sdk::Error::ClientRequest {
+            status_code,
+            error_code: None,
+            error_message: text,
+            error_data: None,
+        }),
+        _ => Err(hyperliquid_rust_sdk::Error::ServerRequest {
+            status_code,
+            error_message: text,
+        }),
+    }
+}
+
 #[uniffi::export]
 pub fn hello_hyperliquid() -> String {
     "Hello from Hyperliquid Rust SDK!".to_string()
 }
 
+/// Simple (non-compounding) yearly rate of a rate paid every `interval_hours`;
+/// Hyperliquid funding is hourly.
+#[uniffi::export]
+pub fn annualize_funding_rate(rate: f64, interval_hours: u32) -> f64 {
+    if interval_hours == 0 {
+        return 0.0;
+    }
+    rate * HOURS_PER_YEAR / interval_hours as f64
+}
+
+/// Numeric copies of string candles, e.g. from `get_candles_range`; candles
+/// with an interval the API does not serve are dropped.
+#[uniffi::export]
+pub fn candles_to_numeric(candles: Vec<CandleData>) -> Vec<Candle> {
+    candles.iter()
+        .filter_map(|candle| Some(Candle::from_data(candle, CandleInterval::from_name(&candle.interval)?)))
+        .collect()
+}
+
+#[uniffi::export]
+pub fn hlp_vault_address() -> String {
+    HLP_VAULT_ADDRESS.to_string()
+}
+
 #[uniffi::export]
 pub fn derive_address_from_private_key(private_key: String) -> String {
-    let wallet: Result<LocalWallet, _> = private_key.parse();
-    match wallet {
-        Ok(w) => format!("{:#x}", w.address()),
+    match LocalSigner::new(&private_key) {
+        Ok(signer) => signer.address(),
         Err(_) => "Invalid private key".to_string(),
     }
 }
\ No newline at end of file
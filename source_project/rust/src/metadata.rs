@@ -0,0 +1,251 @@
+use crate::rate_limit::{info_weight, with_retry, RetryPolicy};
+use hyperliquid_rust_sdk::{Error, InfoClient, Meta};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+pub(crate) const DEFAULT_METADATA_TTL: Duration = Duration::from_secs(300);
+
+// Spot assets are numbered after the perps
+pub(crate) const SPOT_ASSET_OFFSET: u32 = 10000;
+// Prices may have at most this many decimals minus the asset's sz_decimals
+const MAX_PERP_PRICE_DECIMALS: i32 = 6;
+const MAX_SPOT_PRICE_DECIMALS: i32 = 8;
+const MAX_PRICE_SIGNIFICANT_FIGURES: i32 = 5;
+
+// The SDK does not export its spot metadata types, which also drop token details
+#[derive(Deserialize, Debug, Clone)]
+pub(crate) struct SpotMeta {
+    pub universe: Vec<SpotPairMeta>,
+    pub tokens: Vec<SpotTokenMeta>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpotPairMeta {
+    pub tokens: [usize; 2],
+    pub name: String,
+    pub index: usize,
+    pub is_canonical: bool,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpotTokenMeta {
+    pub name: String,
+    pub sz_decimals: u32,
+    pub wei_decimals: u32,
+    pub index: usize,
+    pub token_id: String,
+    pub full_name: Option<String>,
+    pub evm_contract: Option<EvmContract>,
+}
+
+// Linked HyperEVM ERC-20, if any
+#[derive(Deserialize, Debug, Clone)]
+pub(crate) struct EvmContract {
+    pub address: String,
+    pub evm_extra_wei_decimals: i32,
+}
+
+/// Perp and spot universe as of `fetched_at`.
+pub(crate) struct Metadata {
+    pub perp: Meta,
+    pub spot: SpotMeta,
+    fetched_at: Instant,
+    coin_to_asset: HashMap<String, u32>,
+    sz_decimals: HashMap<String, u32>,
+}
+
+impl SpotMeta {
+    pub(crate) fn token(&self, index: usize) -> Option<&SpotTokenMeta> {
+        self.tokens.iter().find(|token| token.index == index)
+    }
+}
+
+impl Metadata {
+    fn new(perp: Meta, spot: SpotMeta) -> Self {
+        let mut coin_to_asset = HashMap::new();
+        let mut sz_decimals = HashMap::new();
+        for (index, asset) in perp.universe.iter().enumerate() {
+            coin_to_asset.insert(asset.name.clone(), index as u32);
+            sz_decimals.insert(asset.name.clone(), asset.sz_decimals);
+        }
+
+        // Spot pairs are known both as "@N" and "BASE/QUOTE", and trade in
+        // units of their base token
+        for pair in &spot.universe {
+            let asset = SPOT_ASSET_OFFSET + pair.index as u32;
+            let (Some(base), Some(quote)) = (spot.token(pair.tokens[0]), spot.token(pair.tokens[1])) else {
+                continue;
+            };
+            for name in [pair.name.clone(), format!("{}/{}", base.name, quote.name)] {
+                coin_to_asset.insert(name.clone(), asset);
+                sz_decimals.insert(name, base.sz_decimals);
+            }
+        }
+
+        Self {
+            perp,
+            spot,
+            fetched_at: Instant::now(),
+            coin_to_asset,
+            sz_decimals,
+        }
+    }
+
+    pub(crate) fn asset_index(&self, coin: &str) -> Option<u32> {
+        self.coin_to_asset.get(coin).copied()
+    }
+
+    pub(crate) fn is_spot(&self, coin: &str) -> bool {
+        self.asset_index(coin).is_some_and(|index| index >= SPOT_ASSET_OFFSET)
+    }
+
+    /// Base and quote token names of a spot coin, under either of its names.
+    pub(crate) fn spot_tokens(&self, coin: &str) -> Option<(&str, &str)> {
+        self.spot.universe.iter().find_map(|pair| {
+            let base = self.spot.token(pair.tokens[0])?;
+            let quote = self.spot.token(pair.tokens[1])?;
+            let matches = pair.name == coin || coin.split_once('/') == Some((base.name.as_str(), quote.name.as_str()));
+            matches.then_some((base.name.as_str(), quote.name.as_str()))
+        })
+    }
+
+    /// Coin name of the pair trading `base` against `quote`, as candles know it.
+    pub(crate) fn spot_pair(&self, base: &str, quote: &str) -> Option<&str> {
+        self.spot
+            .universe
+            .iter()
+            .find(|pair| {
+                self.spot.token(pair.tokens[0]).is_some_and(|token| token.name == base)
+                    && self.spot.token(pair.tokens[1]).is_some_and(|token| token.name == quote)
+            })
+            .map(|pair| pair.name.as_str())
+    }
+
+    /// Name info queries such as l2Book expect: "BASE/QUOTE" spot pairs map to
+    /// their pair name, which is "@N" unless the pair is canonical.
+    pub(crate) fn info_coin<'a>(&'a self, coin: &'a str) -> &'a str {
+        coin.split_once('/')
+            .and_then(|(base, quote)| self.spot_pair(base, quote))
+            .unwrap_or(coin)
+    }
+
+    pub(crate) fn sz_decimals(&self, coin: &str) -> Option<u32> {
+        self.sz_decimals.get(coin).copied()
+    }
+
+    pub(crate) fn round_size(&self, coin: &str, size: f64) -> Option<f64> {
+        let factor = 10f64.powi(self.sz_decimals(coin)? as i32);
+        Some((size * factor).round() / factor)
+    }
+
+    // Exchange rule: at most 5 significant figures and (6 or 8) - sz_decimals
+    // decimals; integer prices are always accepted
+    pub(crate) fn round_price(&self, coin: &str, price: f64) -> Option<f64> {
+        let max_decimals = if self.is_spot(coin) { MAX_SPOT_PRICE_DECIMALS } else { MAX_PERP_PRICE_DECIMALS };
+        let decimals = max_decimals - self.sz_decimals(coin)? as i32;
+        if price <= 0.0 || price.fract() == 0.0 {
+            return Some(price);
+        }
+
+        let magnitude = price.abs().log10().floor() as i32 + 1;
+        let decimals = decimals.min(MAX_PRICE_SIGNIFICANT_FIGURES - magnitude).max(0);
+        let factor = 10f64.powi(decimals);
+        Some((price * factor).round() / factor)
+    }
+}
+
+pub(crate) struct MetadataCache {
+    info: Arc<InfoClient>,
+    // The owning client's policy, so `set_retry_policy` covers metadata too
+    retry_policy: Arc<Mutex<RetryPolicy>>,
+    ttl: Mutex<Duration>,
+    current: RwLock<Option<Arc<Metadata>>>,
+    // Held while fetching, so concurrent callers share one refresh
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+impl MetadataCache {
+    pub(crate) fn new(info: Arc<InfoClient>, retry_policy: Arc<Mutex<RetryPolicy>>) -> Arc<Self> {
+        Arc::new(Self {
+            info,
+            retry_policy,
+            ttl: Mutex::new(DEFAULT_METADATA_TTL),
+            current: RwLock::new(None),
+            refreshing: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    pub(crate) fn ttl(&self) -> Duration {
+        *self.ttl.lock().unwrap()
+    }
+
+    // Floored so the background refresh can never spin
+    pub(crate) fn set_ttl(&self, ttl: Duration) {
+        *self.ttl.lock().unwrap() = ttl.max(Duration::from_secs(1));
+    }
+
+    /// Cached metadata, fetched again once older than the TTL.
+    pub(crate) async fn get(&self) -> Result<Arc<Metadata>, Error> {
+        if let Some(metadata) = self.fresh() {
+            return Ok(metadata);
+        }
+        let _refreshing = self.refreshing.lock().await;
+        // Whoever held the lock may have just refreshed it
+        if let Some(metadata) = self.fresh() {
+            return Ok(metadata);
+        }
+        self.fetch_all().await
+    }
+
+    /// Fetches now, after any refresh already in flight.
+    pub(crate) async fn refresh(&self) -> Result<Arc<Metadata>, Error> {
+        let _refreshing = self.refreshing.lock().await;
+        self.fetch_all().await
+    }
+
+    fn fresh(&self) -> Option<Arc<Metadata>> {
+        let cached = self.current.read().unwrap().clone();
+        cached.filter(|metadata| metadata.fetched_at.elapsed() < self.ttl())
+    }
+
+    async fn fetch_all(&self) -> Result<Arc<Metadata>, Error> {
+        let perp = self.fetch("meta").await?;
+        let spot = self.fetch("spotMeta").await?;
+        let metadata = Arc::new(Metadata::new(perp, spot));
+        *self.current.write().unwrap() = Some(metadata.clone());
+        Ok(metadata)
+    }
+
+    async fn fetch<T: serde::de::DeserializeOwned>(&self, request_type: &str) -> Result<T, Error> {
+        let body = serde_json::json!({ "type": request_type }).to_string();
+        let policy = self.retry_policy.lock().unwrap().clone();
+        let response = with_retry(&policy, info_weight(request_type), || {
+            self.info.http_client.post("/info", body.clone())
+        })
+        .await?;
+        serde_json::from_str(&response).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
+    /// Keeps the cache warm on `runtime` until the cache is dropped.
+    pub(crate) fn spawn_background_refresh(self: &Arc<Self>, runtime: &Runtime) {
+        let cache: Weak<Self> = Arc::downgrade(self);
+        runtime.spawn(async move {
+            loop {
+                let Some(ttl) = cache.upgrade().map(|cache| cache.ttl()) else {
+                    return;
+                };
+                tokio::time::sleep(ttl).await;
+                let Some(cache) = cache.upgrade() else {
+                    return;
+                };
+                // Failures are retried on the next tick or the next `get`
+                let _ = cache.refresh().await;
+            }
+        });
+    }
+}
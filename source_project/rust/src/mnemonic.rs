@@ -0,0 +1,74 @@
+use crate::signer::{LocalSigner, Signer, SignerError};
+use ethers::signers::coins_bip39::{English, Mnemonic};
+use ethers::signers::MnemonicBuilder;
+use std::sync::Arc;
+
+// Standard Ethereum account path, the account index is appended
+pub(crate) const DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
+
+#[derive(uniffi::Record)]
+pub struct DerivedAccount {
+    pub index: u32,
+    pub derivation_path: String,
+    pub address: String,
+}
+
+/// Generates a new English BIP-39 phrase; `word_count` must be 12, 15, 18, 21 or 24.
+#[uniffi::export]
+pub fn generate_mnemonic(word_count: u32) -> Result<String, SignerError> {
+    let mnemonic = Mnemonic::<English>::new_with_count(&mut rand::thread_rng(), word_count as usize)
+        .map_err(|e| invalid_mnemonic(format!("{}", e)))?;
+    Ok(mnemonic.to_phrase())
+}
+
+/// Checks word list membership, word count and checksum of a user-entered phrase.
+#[uniffi::export]
+pub fn validate_mnemonic(phrase: String) -> bool {
+    Mnemonic::<English>::new_from_phrase(&normalize_phrase(&phrase)).is_ok()
+}
+
+#[uniffi::export]
+pub fn mnemonic_signer(phrase: String, index: u32) -> Result<Arc<dyn Signer>, SignerError> {
+    Ok(Arc::new(derive_signer(&phrase, index)?))
+}
+
+/// Addresses of the first `count` accounts, without touching the network.
+#[uniffi::export]
+pub fn derive_mnemonic_accounts(phrase: String, count: u32) -> Result<Vec<DerivedAccount>, SignerError> {
+    (0..count)
+        .map(|index| {
+            let signer = derive_signer(&phrase, index)?;
+            Ok(DerivedAccount {
+                index,
+                derivation_path: format!("{}{}", DERIVATION_PATH_PREFIX, index),
+                address: signer.address(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn derive_signer(phrase: &str, index: u32) -> Result<LocalSigner, SignerError> {
+    let phrase = normalize_phrase(phrase);
+    // Validate up front: the builder only reports a generic wallet error
+    Mnemonic::<English>::new_from_phrase(&phrase).map_err(|e| invalid_mnemonic(format!("{}", e)))?;
+
+    let wallet = MnemonicBuilder::<English>::default()
+        .phrase(phrase.as_str())
+        .derivation_path(&format!("{}{}", DERIVATION_PATH_PREFIX, index))
+        .and_then(|builder| builder.build())
+        .map_err(|e| invalid_mnemonic(format!("{}", e)))?;
+    Ok(LocalSigner::from_wallet(wallet))
+}
+
+// Users paste phrases with stray capitals, newlines and double spaces
+fn normalize_phrase(phrase: &str) -> String {
+    phrase
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn invalid_mnemonic(reason: String) -> SignerError {
+    SignerError::InvalidMnemonic { reason }
+}
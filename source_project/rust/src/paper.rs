@@ -0,0 +1,554 @@
+use crate::pnl::QUOTE_TOKEN;
+use crate::{OpenOrder, OrderLevel, OrderbookData, Position, TokenBalance, UserFill, DUST};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Reported amounts are rounded to this many decimals to hide f64 noise
+const MAX_DECIMALS: usize = 8;
+
+/// Where a paper-trading client's books come from.
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperFeed {
+    /// Books fetched from the exchange whenever an order is placed or the
+    /// account is queried
+    Live,
+    /// Books and mids pushed with `paper_update_book` and `paper_update_mid`,
+    /// e.g. replayed from a recording; nothing is fetched for market data
+    Recorded,
+}
+
+/// Settings of a simulated account for `HyperliquidClient::new_paper_trading`.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct PaperConfig {
+    pub feed: PaperFeed,
+    /// Starting balances by token name; USDC also margins perp positions
+    pub balances: HashMap<String, f64>,
+    pub taker_fee_rate: f64,
+    pub maker_fee_rate: f64,
+    /// Leverage of every perp position
+    pub leverage: u32,
+    /// Address the account reports as its own, the zero address by default
+    pub address: Option<String>,
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            feed: PaperFeed::Live,
+            balances: HashMap::from([(QUOTE_TOKEN.to_string(), 10_000.0)]),
+            // Base tier perp rates
+            taker_fee_rate: 0.00045,
+            maker_fee_rate: 0.00015,
+            leverage: 1,
+            address: None,
+        }
+    }
+}
+
+/// 10,000 USDC at base tier fees and 1x leverage, on live books.
+#[uniffi::export]
+pub fn default_paper_config() -> PaperConfig {
+    PaperConfig::default()
+}
+
+/// What an order trades: a spot pair's tokens, or a perp settled in USDC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PaperMarket {
+    Spot { base: String, quote: String },
+    Perp,
+}
+
+pub(crate) enum PaperOrderOutcome {
+    Filled { oid: u64, size: f64, average_price: f64 },
+    // Possibly after filling part of the order
+    Resting { oid: u64 },
+}
+
+struct Level {
+    price: f64,
+    size: f64,
+}
+
+// Bids best (highest) first, asks best (lowest) first
+#[derive(Default)]
+struct Book {
+    bids: Vec<Level>,
+    asks: Vec<Level>,
+}
+
+struct RestingOrder {
+    oid: u64,
+    coin: String,
+    market: PaperMarket,
+    is_buy: bool,
+    price: f64,
+    size: f64,
+    timestamp: u64,
+}
+
+#[derive(Default)]
+struct PerpPosition {
+    // Negative when short
+    size: f64,
+    entry_price: f64,
+}
+
+struct PaperState {
+    balances: HashMap<String, f64>,
+    orders: Vec<RestingOrder>,
+    positions: HashMap<String, PerpPosition>,
+    fills: Vec<UserFill>,
+    books: HashMap<String, Book>,
+    next_oid: u64,
+    next_tid: u64,
+    // Time of the latest book, which fills and orders are stamped with
+    now: u64,
+}
+
+/// In-memory matching simulator behind a paper-trading client.
+///
+/// Incoming orders take liquidity from the latest book of their coin, which
+/// is used up until the next update. Resting orders fill at their limit price
+/// once a later book crosses them, as far as the crossing size allows. There
+/// is no funding or liquidation.
+pub(crate) struct PaperExchange {
+    pub feed: PaperFeed,
+    pub taker_fee_rate: f64,
+    maker_fee_rate: f64,
+    leverage: f64,
+    state: Mutex<PaperState>,
+}
+
+impl PaperExchange {
+    pub(crate) fn new(config: &PaperConfig) -> Self {
+        Self {
+            feed: config.feed,
+            taker_fee_rate: config.taker_fee_rate,
+            maker_fee_rate: config.maker_fee_rate,
+            leverage: config.leverage.max(1) as f64,
+            state: Mutex::new(PaperState {
+                balances: config.balances.clone(),
+                orders: Vec::new(),
+                positions: HashMap::new(),
+                fills: Vec::new(),
+                books: HashMap::new(),
+                next_oid: 1,
+                next_tid: 1,
+                now: 0,
+            }),
+        }
+    }
+
+    /// Replaces a coin's book with (price, size) levels, then fills resting
+    /// orders it crosses.
+    pub(crate) fn update_book(&self, coin: &str, bids: Vec<(f64, f64)>, asks: Vec<(f64, f64)>, time: u64) {
+        let levels = |levels: Vec<(f64, f64)>| {
+            levels
+                .into_iter()
+                .filter(|(price, size)| *price > 0.0 && *size > DUST)
+                .map(|(price, size)| Level { price, size })
+                .collect::<Vec<_>>()
+        };
+        let mut book = Book {
+            bids: levels(bids),
+            asks: levels(asks),
+        };
+        book.bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        book.asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        let mut state = self.state.lock().unwrap();
+        state.now = state.now.max(time);
+        state.books.insert(coin.to_string(), book);
+        self.fill_resting(&mut state, coin);
+    }
+
+    /// A book of unlimited size at `price` on both sides, for feeds of mids alone.
+    pub(crate) fn update_mid(&self, coin: &str, price: f64, time: u64) {
+        self.update_book(coin, vec![(price, f64::INFINITY)], vec![(price, f64::INFINITY)], time);
+    }
+
+    pub(crate) fn mid(&self, coin: &str) -> Option<f64> {
+        mark_price(&self.state.lock().unwrap(), coin)
+    }
+
+    pub(crate) fn mids(&self) -> Vec<(String, f64)> {
+        let state = self.state.lock().unwrap();
+        let mut mids: Vec<(String, f64)> = state.books.keys().filter_map(|coin| Some((coin.clone(), mark_price(&state, coin)?))).collect();
+        mids.sort_by(|a, b| a.0.cmp(&b.0));
+        mids
+    }
+
+    pub(crate) fn book(&self, coin: &str, depth: usize) -> Option<OrderbookData> {
+        let state = self.state.lock().unwrap();
+        let book = state.books.get(coin)?;
+        let levels = |levels: &[Level]| {
+            levels
+                .iter()
+                .take(depth)
+                .map(|level| OrderLevel {
+                    price: decimal(level.price),
+                    // Books made from a mid have no known depth
+                    size: if level.size.is_finite() { decimal(level.size) } else { "0".to_string() },
+                })
+                .collect()
+        };
+        Some(OrderbookData {
+            bids: levels(&book.bids),
+            asks: levels(&book.asks),
+        })
+    }
+
+    /// Coins whose books matter to the account: open orders and positions.
+    pub(crate) fn active_coins(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        let mut coins: Vec<String> = state.orders.iter().map(|order| order.coin.clone()).collect();
+        coins.extend(state.positions.keys().cloned());
+        coins.sort();
+        coins.dedup();
+        coins
+    }
+
+    /// Places a limit order; `time_in_force` is "Gtc", "Ioc" or "Alo" as on
+    /// the exchange.
+    pub(crate) fn place_order(&self, coin: &str, market: PaperMarket, is_buy: bool, size: f64, price: f64, time_in_force: &str) -> Result<PaperOrderOutcome, String> {
+        if size <= 0.0 {
+            return Err("Order size must be positive".to_string());
+        }
+        // Dust would match nothing yet report a fill
+        if size <= DUST {
+            return Err(format!("Order size too small: {}", size));
+        }
+        if price <= 0.0 {
+            return Err("Order price must be positive".to_string());
+        }
+        if !["Gtc", "Ioc", "Alo"].contains(&time_in_force) {
+            return Err(format!("Invalid time in force: {}", time_in_force));
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let Some(book) = state.books.get(coin) else {
+            return Err(format!("No book for {}", coin));
+        };
+        let best_opposite = if is_buy { book.asks.first() } else { book.bids.first() }.map(|level| level.price);
+        let crosses = best_opposite.is_some_and(|best| if is_buy { best <= price } else { best >= price });
+        if time_in_force == "Alo" && crosses {
+            return Err("Post only order would have immediately matched".to_string());
+        }
+        if time_in_force == "Ioc" && !crosses {
+            return Err("Order could not immediately match against any resting orders".to_string());
+        }
+        self.check_funds(&state, coin, &market, is_buy, size, price)?;
+
+        let oid = state.next_oid;
+        state.next_oid += 1;
+        let book = state.books.get_mut(coin).expect("book checked above");
+        let taken = take_liquidity(book, is_buy, size, price);
+
+        let mut filled = 0.0;
+        let mut notional = 0.0;
+        for (fill_price, fill_size) in taken {
+            self.settle(&mut state, coin, &market, is_buy, fill_price, fill_size, oid, true);
+            filled += fill_size;
+            notional += fill_price * fill_size;
+        }
+
+        let remaining = size - filled;
+        if remaining > DUST && time_in_force != "Ioc" {
+            let timestamp = state.now;
+            state.orders.push(RestingOrder {
+                oid,
+                coin: coin.to_string(),
+                market,
+                is_buy,
+                price,
+                size: remaining,
+                timestamp,
+            });
+            return Ok(PaperOrderOutcome::Resting { oid });
+        }
+        Ok(PaperOrderOutcome::Filled {
+            oid,
+            size: filled,
+            average_price: notional / filled,
+        })
+    }
+
+    pub(crate) fn cancel_order(&self, coin: &str, oid: u64) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.orders.len();
+        state.orders.retain(|order| !(order.oid == oid && order.coin == coin));
+        if state.orders.len() == before {
+            return Err("Order was never placed, already canceled, or filled".to_string());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn balances(&self) -> Vec<TokenBalance> {
+        let state = self.state.lock().unwrap();
+        let mut tokens: Vec<&String> = state.balances.keys().collect();
+        tokens.sort();
+        tokens
+            .into_iter()
+            .map(|token| TokenBalance {
+                coin: token.clone(),
+                total: decimal(state.balances[token]),
+                hold: decimal(self.hold(&state, token)),
+            })
+            .collect()
+    }
+
+    // Marked at the mid, or the entry price without a book
+    pub(crate) fn positions(&self) -> Vec<Position> {
+        let state = self.state.lock().unwrap();
+        let mut coins: Vec<&String> = state.positions.keys().collect();
+        coins.sort();
+        coins
+            .into_iter()
+            .map(|coin| {
+                let position = &state.positions[coin];
+                let mark = mark_price(&state, coin).unwrap_or(position.entry_price);
+                let position_value = position.size.abs() * mark;
+                let unrealized_pnl = (mark - position.entry_price) * position.size;
+                let margin_used = position_value / self.leverage;
+                let initial_margin = position.size.abs() * position.entry_price / self.leverage;
+                Position {
+                    coin: coin.clone(),
+                    size: decimal(position.size),
+                    entry_price: Some(decimal(position.entry_price)),
+                    position_value: decimal(position_value),
+                    unrealized_pnl: decimal(unrealized_pnl),
+                    return_on_equity: decimal(if initial_margin > 0.0 { unrealized_pnl / initial_margin } else { 0.0 }),
+                    liquidation_price: None,
+                    margin_used: decimal(margin_used),
+                    leverage: self.leverage as u32,
+                    leverage_type: "cross".to_string(),
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn open_orders(&self) -> Vec<OpenOrder> {
+        let state = self.state.lock().unwrap();
+        state
+            .orders
+            .iter()
+            .map(|order| OpenOrder {
+                coin: order.coin.clone(),
+                side: if order.is_buy { "B" } else { "A" }.to_string(),
+                limit_price: decimal(order.price),
+                size: decimal(order.size),
+                order_id: order.oid,
+                timestamp: order.timestamp,
+            })
+            .collect()
+    }
+
+    pub(crate) fn fills(&self, start_time: u64, end_time: Option<u64>) -> Vec<UserFill> {
+        let state = self.state.lock().unwrap();
+        state
+            .fills
+            .iter()
+            .filter(|fill| fill.time >= start_time && end_time.is_none_or(|end| fill.time <= end))
+            .cloned()
+            .collect()
+    }
+
+    // Amount of a token set aside: spot orders' funds, and for USDC the
+    // margin of perp positions and orders
+    fn hold(&self, state: &PaperState, token: &str) -> f64 {
+        let mut hold = 0.0;
+        for order in &state.orders {
+            match &order.market {
+                PaperMarket::Spot { base, quote } => {
+                    if order.is_buy && quote == token {
+                        hold += order.price * order.size;
+                    } else if !order.is_buy && base == token {
+                        hold += order.size;
+                    }
+                }
+                PaperMarket::Perp if token == QUOTE_TOKEN => hold += order.price * order.size / self.leverage,
+                PaperMarket::Perp => {}
+            }
+        }
+        if token == QUOTE_TOKEN {
+            for (coin, position) in &state.positions {
+                let mark = mark_price(state, coin).unwrap_or(position.entry_price);
+                hold += position.size.abs() * mark / self.leverage;
+            }
+        }
+        hold
+    }
+
+    fn check_funds(&self, state: &PaperState, coin: &str, market: &PaperMarket, is_buy: bool, size: f64, price: f64) -> Result<(), String> {
+        let available = |token: &str| state.balances.get(token).copied().unwrap_or(0.0) - self.hold(state, token);
+        let (token, needed) = match market {
+            PaperMarket::Spot { quote, .. } if is_buy => (quote.as_str(), price * size),
+            PaperMarket::Spot { base, .. } => (base.as_str(), size),
+            PaperMarket::Perp => {
+                // Only the part that adds to the position needs margin
+                let position = state.positions.get(coin).map_or(0.0, |position| position.size);
+                let signed = if is_buy { size } else { -size };
+                let reducing = if position * signed < 0.0 { position.abs().min(size) } else { 0.0 };
+                let unrealized: f64 = state
+                    .positions
+                    .iter()
+                    .map(|(coin, position)| (mark_price(state, coin).unwrap_or(position.entry_price) - position.entry_price) * position.size)
+                    .sum();
+                let needed = (size - reducing) * price / self.leverage;
+                if needed > DUST && available(QUOTE_TOKEN) + unrealized < needed {
+                    return Err("Insufficient margin to place order".to_string());
+                }
+                return Ok(());
+            }
+        };
+        if available(token) + DUST < needed {
+            return Err(format!("Insufficient {} balance", token));
+        }
+        Ok(())
+    }
+
+    // Resting orders of a coin, oldest first, against its new book
+    fn fill_resting(&self, state: &mut PaperState, coin: &str) {
+        let mut index = 0;
+        while index < state.orders.len() {
+            if state.orders[index].coin != coin {
+                index += 1;
+                continue;
+            }
+            let (oid, market, is_buy, price, size) = {
+                let order = &state.orders[index];
+                (order.oid, order.market.clone(), order.is_buy, order.price, order.size)
+            };
+            let book = state.books.get_mut(coin).expect("book updated by the caller");
+            let filled: f64 = take_liquidity(book, is_buy, size, price).iter().map(|(_, size)| size).sum();
+            if filled > DUST {
+                self.settle(state, coin, &market, is_buy, price, filled, oid, false);
+            }
+
+            if size - filled > DUST {
+                state.orders[index].size = size - filled;
+                index += 1;
+            } else {
+                state.orders.remove(index);
+            }
+        }
+    }
+
+    // Moves funds for one fill and records it as the exchange would report it
+    #[allow(clippy::too_many_arguments)]
+    fn settle(&self, state: &mut PaperState, coin: &str, market: &PaperMarket, is_buy: bool, price: f64, size: f64, oid: u64, crossed: bool) {
+        let fee_rate = if crossed { self.taker_fee_rate } else { self.maker_fee_rate };
+        let (start_position, direction, closed_pnl, fee, fee_token) = match market {
+            PaperMarket::Spot { base, quote } => {
+                let start_position = state.balances.get(base).copied().unwrap_or(0.0);
+                // Buyers pay fees in the token they receive, sellers in the quote
+                let (fee, fee_token) = if is_buy {
+                    *state.balances.entry(quote.clone()).or_default() -= price * size;
+                    *state.balances.entry(base.clone()).or_default() += size * (1.0 - fee_rate);
+                    (size * fee_rate, base.clone())
+                } else {
+                    *state.balances.entry(base.clone()).or_default() -= size;
+                    *state.balances.entry(quote.clone()).or_default() += price * size * (1.0 - fee_rate);
+                    (price * size * fee_rate, quote.clone())
+                };
+                (start_position, if is_buy { "Buy" } else { "Sell" }.to_string(), 0.0, fee, fee_token)
+            }
+            PaperMarket::Perp => {
+                let position = state.positions.entry(coin.to_string()).or_default();
+                let start_position = position.size;
+                let signed = if is_buy { size } else { -size };
+                let closing = if start_position * signed < 0.0 { start_position.abs().min(size) } else { 0.0 };
+                let closed_pnl = (price - position.entry_price) * closing * start_position.signum();
+
+                let end_position = start_position + signed;
+                position.entry_price = if closing == 0.0 {
+                    (position.entry_price * start_position.abs() + price * size) / end_position.abs()
+                } else if end_position.abs() > DUST && end_position.signum() != start_position.signum() {
+                    price
+                } else {
+                    position.entry_price
+                };
+                position.size = end_position;
+                if end_position.abs() <= DUST {
+                    state.positions.remove(coin);
+                }
+
+                let fee = price * size * fee_rate;
+                *state.balances.entry(QUOTE_TOKEN.to_string()).or_default() += closed_pnl - fee;
+                (start_position, perp_direction(start_position, end_position), closed_pnl, fee, QUOTE_TOKEN.to_string())
+            }
+        };
+
+        let tid = state.next_tid;
+        state.next_tid += 1;
+        state.fills.push(UserFill {
+            coin: coin.to_string(),
+            px: decimal(price),
+            sz: decimal(size),
+            side: if is_buy { "B" } else { "A" }.to_string(),
+            time: state.now,
+            start_position: decimal(start_position),
+            dir: direction,
+            closed_pnl: decimal(closed_pnl),
+            hash: format!("0x{:064x}", tid),
+            oid,
+            crossed,
+            fee: Some(decimal(fee)),
+            tid: Some(tid),
+            fee_token: Some(fee_token),
+        });
+    }
+}
+
+// Takes up to `size` from the opposite side at prices no worse than `limit`,
+// returning (price, size) per level
+fn take_liquidity(book: &mut Book, is_buy: bool, size: f64, limit: f64) -> Vec<(f64, f64)> {
+    let levels = if is_buy { &mut book.asks } else { &mut book.bids };
+    let mut taken = Vec::new();
+    let mut remaining = size;
+    while remaining > DUST {
+        let Some(level) = levels.first_mut() else {
+            break;
+        };
+        if (is_buy && level.price > limit) || (!is_buy && level.price < limit) {
+            break;
+        }
+        let take = remaining.min(level.size);
+        taken.push((level.price, take));
+        level.size -= take;
+        remaining -= take;
+        if level.size <= DUST {
+            levels.remove(0);
+        }
+    }
+    taken
+}
+
+fn mark_price(state: &PaperState, coin: &str) -> Option<f64> {
+    let book = state.books.get(coin)?;
+    Some((book.bids.first()?.price + book.asks.first()?.price) / 2.0)
+}
+
+fn perp_direction(start: f64, end: f64) -> String {
+    let direction = if start > DUST && end < -DUST {
+        "Long > Short"
+    } else if start < -DUST && end > DUST {
+        "Short > Long"
+    } else if end.abs() > start.abs() {
+        if end > 0.0 { "Open Long" } else { "Open Short" }
+    } else if start > 0.0 {
+        "Close Long"
+    } else {
+        "Close Short"
+    };
+    direction.to_string()
+}
+
+pub(crate) fn decimal(value: f64) -> String {
+    let formatted = format!("{:.*}", MAX_DECIMALS, value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "" | "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{number, PortfolioPeriod};
+use std::collections::HashMap;
+
+const YEAR_MS: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Performance over one portfolio period. Returns and drawdowns are fractions
+/// (0.1 is 10%); volatility and Sharpe are annualized.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct PerformanceMetrics {
+    pub period: String,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub start_value: f64,
+    pub end_value: f64,
+    pub pnl: f64,
+    // Time-weighted, so deposits and withdrawals do not count as returns
+    pub total_return: f64,
+    // Largest fall from a peak of the return index, positive
+    pub max_drawdown: f64,
+    pub drawdown_peak_time: Option<u64>,
+    pub drawdown_trough_time: Option<u64>,
+    pub volatility: f64,
+    // None when returns never varied
+    pub sharpe_ratio: Option<f64>,
+    pub return_count: u32,
+}
+
+/// Metrics of a portfolio period from `get_portfolio_history`.
+///
+/// Each step's return is its PnL over the account value at the step's start,
+/// which leaves out deposits and withdrawals. Steps starting from an empty
+/// account are skipped. Volatility and Sharpe are annualized from the average
+/// spacing of the points; `risk_free_rate` is annual, e.g. 0.04.
+#[uniffi::export]
+pub fn compute_performance(period: PortfolioPeriod, risk_free_rate: f64) -> PerformanceMetrics {
+    let pnl_at: HashMap<u64, f64> = period.pnl_history.iter().map(|point| (point.time, number(&point.value))).collect();
+    let points: Vec<(u64, f64, f64)> = period
+        .account_value_history
+        .iter()
+        .filter_map(|point| Some((point.time, number(&point.value), *pnl_at.get(&point.time)?)))
+        .collect();
+
+    let mut returns = Vec::new();
+    let mut index = 1.0;
+    let (mut peak, mut peak_time) = (1.0, points.first().map(|point| point.0));
+    let mut max_drawdown = 0.0;
+    let (mut drawdown_peak_time, mut drawdown_trough_time) = (None, None);
+    for pair in points.windows(2) {
+        let (_, previous_value, previous_pnl) = pair[0];
+        let (time, _, pnl) = pair[1];
+        if previous_value <= 0.0 {
+            continue;
+        }
+        let step_return = (pnl - previous_pnl) / previous_value;
+        returns.push(step_return);
+
+        index *= 1.0 + step_return;
+        if index > peak {
+            peak = index;
+            peak_time = Some(time);
+        }
+        let drawdown = 1.0 - index / peak;
+        if drawdown > max_drawdown {
+            max_drawdown = drawdown;
+            drawdown_peak_time = peak_time;
+            drawdown_trough_time = Some(time);
+        }
+    }
+
+    let (start_time, start_value, start_pnl) = points.first().copied().unwrap_or_default();
+    let (end_time, end_value, end_pnl) = points.last().copied().unwrap_or_default();
+
+    let count = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / count.max(1.0);
+    // Sample standard deviation
+    let variance = if count > 1.0 { returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (count - 1.0) } else { 0.0 };
+    let periods_per_year = if count > 0.0 && end_time > start_time { YEAR_MS / ((end_time - start_time) as f64 / (points.len() - 1) as f64) } else { 0.0 };
+    let volatility = variance.sqrt() * periods_per_year.sqrt();
+    let sharpe_ratio = (volatility > 0.0).then(|| (mean * periods_per_year - risk_free_rate) / volatility);
+
+    PerformanceMetrics {
+        period: period.period,
+        start_time,
+        end_time,
+        start_value,
+        end_value,
+        pnl: end_pnl - start_pnl,
+        total_return: index - 1.0,
+        max_drawdown,
+        drawdown_peak_time,
+        drawdown_trough_time,
+        volatility,
+        sharpe_ratio,
+        return_count: returns.len() as u32,
+    }
+}
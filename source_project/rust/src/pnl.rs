@@ -0,0 +1,202 @@
+use crate::{number, UserFill, DUST};
+use std::collections::{HashMap, VecDeque};
+
+pub(crate) const QUOTE_TOKEN: &str = "USDC";
+
+/// Which lots a reducing fill closes.
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostBasisMethod {
+    /// Oldest lots first
+    Fifo,
+    /// One lot at the average entry price, as the exchange reports closed PnL
+    AverageCost,
+}
+
+/// PnL of one coin after replaying its fills; USDC amounts unless noted.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct CoinPnl {
+    pub coin: String,
+    // Negative when short
+    pub position_size: f64,
+    pub average_entry_price: f64,
+    pub cost_basis: f64,
+    // Before fees
+    pub realized_pnl: f64,
+    pub fees_paid: f64,
+    pub current_price: Option<f64>,
+    // Zero without a current price
+    pub unrealized_pnl: f64,
+    pub fill_count: u32,
+    // Fills began with a position already open, whose cost is assumed to be
+    // the first fill's price
+    pub incomplete_history: bool,
+}
+
+struct Lot {
+    // Signed, negative for shorts
+    size: f64,
+    price: f64,
+    opened: u64,
+}
+
+/// Part of a lot closed by a later fill.
+pub(crate) struct Disposal {
+    // Signed like the lot, negative when a short was covered
+    pub size: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub opened: u64,
+    pub closed: u64,
+}
+
+pub(crate) struct Ledger {
+    method: CostBasisMethod,
+    lots: VecDeque<Lot>,
+    realized_pnl: f64,
+    fees_paid: f64,
+    fill_count: u32,
+    incomplete_history: bool,
+}
+
+impl Ledger {
+    pub(crate) fn new(method: CostBasisMethod) -> Self {
+        Self {
+            method,
+            lots: VecDeque::new(),
+            realized_pnl: 0.0,
+            fees_paid: 0.0,
+            fill_count: 0,
+            incomplete_history: false,
+        }
+    }
+
+    fn position(&self) -> f64 {
+        self.lots.iter().map(|lot| lot.size).sum()
+    }
+
+    // Adds a signed size at `price`, returning the lots it closed
+    fn apply(&mut self, signed_size: f64, price: f64, time: u64) -> Vec<Disposal> {
+        let mut remaining = signed_size;
+        let mut disposals = Vec::new();
+
+        // Close lots on the other side first; with average cost there is only one
+        while remaining.abs() > DUST {
+            let Some(lot) = self.lots.front_mut() else {
+                break;
+            };
+            if lot.size.signum() == remaining.signum() {
+                break;
+            }
+            let closed = lot.size.signum() * remaining.abs().min(lot.size.abs());
+            self.realized_pnl += (price - lot.price) * closed;
+            disposals.push(Disposal {
+                size: closed,
+                entry_price: lot.price,
+                exit_price: price,
+                opened: lot.opened,
+                closed: time,
+            });
+            lot.size -= closed;
+            remaining += closed;
+            if lot.size.abs() <= DUST {
+                self.lots.pop_front();
+            }
+        }
+
+        if remaining.abs() <= DUST {
+            return disposals;
+        }
+        // An averaged lot keeps the time it was first opened
+        match (self.method, self.lots.front_mut()) {
+            (CostBasisMethod::AverageCost, Some(lot)) => {
+                lot.price = (lot.price * lot.size + price * remaining) / (lot.size + remaining);
+                lot.size += remaining;
+            }
+            _ => self.lots.push_back(Lot {
+                size: remaining,
+                price,
+                opened: time,
+            }),
+        }
+        disposals
+    }
+
+    /// Applies a fill of this ledger's coin, returning the lots it closed.
+    pub(crate) fn apply_fill(&mut self, fill: &UserFill) -> Vec<Disposal> {
+        let price = number(&fill.px);
+        let size = number(&fill.sz);
+
+        if self.fill_count == 0 {
+            let start_position = number(&fill.start_position);
+            if start_position.abs() > DUST {
+                self.apply(start_position, price, fill.time);
+                self.incomplete_history = true;
+            }
+        }
+        self.fill_count += 1;
+
+        let signed_size = if fill.side == "B" { size } else { -size };
+        let fee = fill.fee.as_deref().map_or(0.0, number);
+        if fill.fee_token.as_deref().is_none_or(|token| token == QUOTE_TOKEN) {
+            self.fees_paid += fee;
+            self.apply(signed_size, price, fill.time)
+        } else {
+            self.fees_paid += fee * price;
+            self.apply(signed_size - fee, price, fill.time)
+        }
+    }
+
+    pub(crate) fn incomplete_history(&self) -> bool {
+        self.incomplete_history
+    }
+
+    fn summary(&self, coin: String, current_price: Option<f64>) -> CoinPnl {
+        let position_size = self.position();
+        let cost_basis: f64 = self.lots.iter().map(|lot| lot.size.abs() * lot.price).sum();
+        let average_entry_price = if position_size.abs() > DUST { cost_basis / position_size.abs() } else { 0.0 };
+        let unrealized_pnl = current_price.map_or(0.0, |price| (price - average_entry_price) * position_size);
+
+        CoinPnl {
+            coin,
+            position_size: if position_size.abs() > DUST { position_size } else { 0.0 },
+            average_entry_price,
+            cost_basis,
+            realized_pnl: self.realized_pnl,
+            fees_paid: self.fees_paid,
+            current_price,
+            unrealized_pnl,
+            fill_count: self.fill_count,
+            incomplete_history: self.incomplete_history,
+        }
+    }
+}
+
+/// Replays fills per coin, in time order, into positions and PnL.
+///
+/// `current_prices` maps coins to their mid, as `get_all_mids` returns them.
+/// Fees charged in the traded token (spot buys) are valued at the fill price
+/// and reduce the position received.
+#[uniffi::export]
+pub fn compute_pnl(fills: Vec<UserFill>, current_prices: HashMap<String, f64>, method: CostBasisMethod) -> Vec<CoinPnl> {
+    let mut fills = fills;
+    fills.sort_by_key(|fill| (fill.time, fill.tid.unwrap_or(0)));
+
+    let mut ledgers: HashMap<String, Ledger> = HashMap::new();
+    let mut order: Vec<String> = Vec::new();
+    for fill in &fills {
+        let ledger = ledgers.entry(fill.coin.clone()).or_insert_with(|| {
+            order.push(fill.coin.clone());
+            Ledger::new(method)
+        });
+        ledger.apply_fill(fill);
+    }
+
+    order
+        .into_iter()
+        .map(|coin| {
+            let current_price = current_prices.get(&coin).copied();
+            ledgers[&coin].summary(coin, current_price)
+        })
+        .collect()
+}
+
@@ -0,0 +1,180 @@
+use ethers::types::H160;
+use hyperliquid_rust_sdk::Error;
+use rand::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// Hyperliquid allows 1200 weight per minute per IP, across all REST requests
+const IP_WEIGHT_PER_MINUTE: u32 = 1200;
+const WINDOW: Duration = Duration::from_secs(60);
+// Once an address has used up its request budget, the exchange still accepts
+// one action every 10 seconds
+const EXHAUSTED_ADDRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Retry policy for info requests. Exchange actions are never retried.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 250,
+            max_backoff_ms: 8_000,
+        }
+    }
+}
+
+struct AddressBudget {
+    remaining: u64,
+    last_action: Option<Instant>,
+}
+
+/// Shared by every client in the process, since the per-IP budget is.
+pub(crate) struct RateLimiter {
+    ip_window: Mutex<VecDeque<(Instant, u32)>>,
+    addresses: Mutex<HashMap<H160, AddressBudget>>,
+}
+
+pub(crate) fn limiter() -> &'static RateLimiter {
+    static LIMITER: OnceLock<RateLimiter> = OnceLock::new();
+    LIMITER.get_or_init(|| RateLimiter {
+        ip_window: Mutex::new(VecDeque::new()),
+        addresses: Mutex::new(HashMap::new()),
+    })
+}
+
+impl RateLimiter {
+    /// Waits until `weight` fits into the per-IP budget of the last minute.
+    pub(crate) async fn acquire(&self, weight: u32) {
+        let weight = weight.min(IP_WEIGHT_PER_MINUTE);
+        loop {
+            let wait = {
+                let mut window = self.ip_window.lock().unwrap();
+                let now = Instant::now();
+                while window.front().is_some_and(|(at, _)| now.duration_since(*at) >= WINDOW) {
+                    window.pop_front();
+                }
+
+                let used: u32 = window.iter().map(|(_, w)| w).sum();
+                if used + weight <= IP_WEIGHT_PER_MINUTE {
+                    window.push_back((now, weight));
+                    return;
+                }
+                let (oldest, _) = window.front().copied().expect("Window cannot be empty when over budget");
+                WINDOW - now.duration_since(oldest)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    // Weight that depends on the response size is only known afterwards
+    pub(crate) fn charge(&self, weight: u32) {
+        if weight > 0 {
+            self.ip_window.lock().unwrap().push_back((Instant::now(), weight));
+        }
+    }
+
+    pub(crate) fn set_address_budget(&self, address: H160, remaining: u64) {
+        let mut addresses = self.addresses.lock().unwrap();
+        let budget = addresses.entry(address).or_insert(AddressBudget {
+            remaining,
+            last_action: None,
+        });
+        budget.remaining = remaining;
+    }
+
+    /// Spends one request of the address budget, pacing actions once it is used up.
+    pub(crate) async fn acquire_action(&self, address: H160, weight: u32) {
+        let wait = {
+            let mut addresses = self.addresses.lock().unwrap();
+            match addresses.get_mut(&address) {
+                Some(budget) if budget.remaining == 0 => {
+                    let wait = budget
+                        .last_action
+                        .map(|last| EXHAUSTED_ADDRESS_INTERVAL.saturating_sub(last.elapsed()))
+                        .unwrap_or_default();
+                    budget.last_action = Some(Instant::now() + wait);
+                    wait
+                }
+                Some(budget) => {
+                    budget.remaining -= 1;
+                    budget.last_action = Some(Instant::now());
+                    Duration::ZERO
+                }
+                // Unknown budget: let the exchange be the judge
+                None => Duration::ZERO,
+            }
+        };
+        tokio::time::sleep(wait).await;
+        self.acquire(weight).await;
+    }
+}
+
+/// Weight of an info request, from Hyperliquid's published limits.
+pub(crate) fn info_weight(request_type: &str) -> u32 {
+    match request_type {
+        "l2Book" | "allMids" | "clearinghouseState" | "orderStatus" | "spotClearinghouseState" | "exchangeStatus" => 2,
+        "userRole" => 60,
+        _ => 20,
+    }
+}
+
+/// Extra weight charged per item returned by paginated info requests.
+pub(crate) fn response_weight(request_type: &str, items: usize) -> u32 {
+    let per_weight = match request_type {
+        "candleSnapshot" => 60,
+        "recentTrades" | "historicalOrders" | "userFills" | "userFillsByTime" | "fundingHistory" | "userFunding"
+        | "nonUserFundingUpdates" | "twapHistory" | "userTwapSliceFills" | "delegatorHistory" => 20,
+        _ => return 0,
+    };
+    (items / per_weight) as u32
+}
+
+/// Exchange actions weigh 1, plus 1 per 40 orders or cancels in a batch.
+pub(crate) fn action_weight(batch_length: usize) -> u32 {
+    1 + (batch_length / 40) as u32
+}
+
+// Rate limiting, server errors and network failures are worth another try;
+// anything else would fail the same way again
+fn is_retryable(error: &Error) -> bool {
+    match error {
+        Error::ClientRequest { status_code, .. } => *status_code == 429,
+        Error::ServerRequest { .. } | Error::GenericRequest(_) => true,
+        _ => false,
+    }
+}
+
+// Exponential backoff with jitter over the upper half, so clients that failed
+// together do not retry together
+fn backoff(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let exponential = policy.initial_backoff_ms.saturating_mul(1u64 << attempt.min(16));
+    let capped = exponential.min(policy.max_backoff_ms).max(1);
+    Duration::from_millis(rand::thread_rng().gen_range(capped / 2..=capped))
+}
+
+/// Runs a read-only info request under the rate limiter, retrying transient failures.
+pub(crate) async fn with_retry<T, F, Fut>(policy: &RetryPolicy, weight: u32, mut request: F) -> Result<T, Error>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut attempt = 0;
+    loop {
+        limiter().acquire(weight).await;
+        match request().await {
+            Err(error) if attempt < policy.max_retries && is_retryable(&error) => {
+                tokio::time::sleep(backoff(policy, attempt)).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
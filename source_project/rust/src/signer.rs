@@ -0,0 +1,117 @@
+use ethers::signers::{LocalWallet, Signer as _};
+use ethers::types::{Address, Signature, H256};
+use std::sync::Arc;
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum SignerError {
+    #[error("Invalid private key: {reason}")]
+    InvalidKey { reason: String },
+    #[error("Invalid mnemonic: {reason}")]
+    InvalidMnemonic { reason: String },
+    #[error("Invalid keystore: {reason}")]
+    InvalidKeystore { reason: String },
+    #[error("Invalid address: {reason}")]
+    InvalidAddress { reason: String },
+    #[error("Client is not bound to an address. Use new_watch_only() or a wallet constructor.")]
+    NotBound,
+    #[error("Invalid action: {reason}")]
+    InvalidAction { reason: String },
+    #[error("Request failed: {reason}")]
+    RequestFailed { reason: String },
+    #[error("Signing failed: {reason}")]
+    SigningFailed { reason: String },
+    #[error("Signer callback failed: {reason}")]
+    Unexpected { reason: String },
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for SignerError {
+    fn from(e: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        SignerError::Unexpected { reason: e.reason }
+    }
+}
+
+/// Signs exchange actions on behalf of a single account.
+///
+/// Implement this in Swift to keep keys in the Keychain or Secure Enclave;
+/// the crate only ever hands over 32-byte EIP-712 digests.
+#[uniffi::export(with_foreign)]
+pub trait Signer: Send + Sync {
+    /// 0x-prefixed address of the account this signer signs for.
+    fn address(&self) -> String;
+
+    /// Signs a 32-byte digest, returning the 65-byte `r || s || v` signature (v = 27 or 28).
+    fn sign_hash(&self, hash: Vec<u8>) -> Result<Vec<u8>, SignerError>;
+}
+
+/// Built-in signer backed by an in-memory private key.
+pub struct LocalSigner {
+    wallet: LocalWallet,
+}
+
+impl LocalSigner {
+    pub fn new(private_key: &str) -> Result<Self, SignerError> {
+        let wallet: LocalWallet = private_key.parse().map_err(|e| SignerError::InvalidKey {
+            reason: format!("{}", e),
+        })?;
+        Ok(Self { wallet })
+    }
+
+    pub(crate) fn from_wallet(wallet: LocalWallet) -> Self {
+        Self { wallet }
+    }
+}
+
+impl Signer for LocalSigner {
+    fn address(&self) -> String {
+        format!("{:#x}", self.wallet.address())
+    }
+
+    fn sign_hash(&self, hash: Vec<u8>) -> Result<Vec<u8>, SignerError> {
+        let hash = hash_from_bytes(&hash)?;
+        let signature = self.wallet.sign_hash(hash).map_err(|e| SignerError::SigningFailed {
+            reason: format!("{}", e),
+        })?;
+        Ok(signature.to_vec())
+    }
+}
+
+#[uniffi::export]
+pub fn new_local_signer(private_key: String) -> Result<Arc<dyn Signer>, SignerError> {
+    Ok(Arc::new(LocalSigner::new(&private_key)?))
+}
+
+fn hash_from_bytes(bytes: &[u8]) -> Result<H256, SignerError> {
+    if bytes.len() != 32 {
+        return Err(SignerError::SigningFailed {
+            reason: format!("Expected a 32-byte hash, got {} bytes", bytes.len()),
+        });
+    }
+    Ok(H256::from_slice(bytes))
+}
+
+pub(crate) fn signer_address(signer: &dyn Signer) -> Result<Address, SignerError> {
+    signer.address().parse().map_err(|_| SignerError::Unexpected {
+        reason: format!("Signer returned an invalid address: {}", signer.address()),
+    })
+}
+
+// Foreign signers are untrusted, so every signature is recovered and checked
+// against the signer's own address before it is sent anywhere.
+pub(crate) fn sign_digest(signer: &dyn Signer, digest: H256) -> Result<Signature, SignerError> {
+    let bytes = signer.sign_hash(digest.as_bytes().to_vec())?;
+    let signature = Signature::try_from(bytes.as_slice()).map_err(|e| SignerError::SigningFailed {
+        reason: format!("Malformed signature: {}", e),
+    })?;
+
+    let expected = signer_address(signer)?;
+    let recovered = signature.recover(digest).map_err(|e| SignerError::SigningFailed {
+        reason: format!("Signature does not recover: {}", e),
+    })?;
+    if recovered != expected {
+        return Err(SignerError::SigningFailed {
+            reason: format!("Signature recovers to {:#x}, expected {:#x}", recovered, expected),
+        });
+    }
+
+    Ok(signature)
+}
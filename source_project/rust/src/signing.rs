@@ -0,0 +1,301 @@
+use crate::exchange::{
+    builder_fee_rate, hyperliquid_chain, next_nonce, sign_payload, Action, ApproveBuilderFee, BulkCancel, BulkOrder,
+    CancelWire, CreateSubAccount, ExchangePayload, OrderTypeWire, OrderWire, SubAccountSpotTransfer, SubAccountTransfer,
+    max_builder_fee_tenths_bps, VaultTransfer, SIGNATURE_CHAIN_ID,
+};
+use crate::metadata::SPOT_ASSET_OFFSET;
+use crate::signer::{Signer, SignerError};
+use crate::BuilderFee;
+use ethers::types::{Signature, H160};
+use ethers::utils::hex;
+use hyperliquid_rust_sdk::{BuilderInfo, SpotSend, UsdSend, Withdraw3};
+use std::sync::Arc;
+
+/// An exchange action that can be built without a network connection.
+///
+/// Prices and sizes are signed exactly as given, so pass them in the
+/// exchange's wire format (at most 8 decimals, no trailing zeros).
+#[derive(uniffi::Enum, Debug, Clone)]
+pub enum ActionRequest {
+    Order {
+        asset: u32,
+        is_buy: bool,
+        limit_price: String,
+        size: String,
+        reduce_only: bool,
+        time_in_force: String,
+        cloid: Option<String>,
+        builder: Option<BuilderFee>,
+    },
+    Cancel {
+        asset: u32,
+        order_id: u64,
+    },
+    UsdSend {
+        destination: String,
+        amount: String,
+    },
+    SpotSend {
+        destination: String,
+        token: String,
+        amount: String,
+    },
+    Withdraw {
+        destination: String,
+        amount: String,
+    },
+    VaultTransfer {
+        vault_address: String,
+        is_deposit: bool,
+        usd: u64,
+    },
+    CreateSubAccount {
+        name: String,
+    },
+    SubAccountTransfer {
+        sub_account: String,
+        is_deposit: bool,
+        usd: u64,
+    },
+    /// `token` is "NAME:0x<token id>", as in spot metadata.
+    SubAccountSpotTransfer {
+        sub_account: String,
+        is_deposit: bool,
+        token: String,
+        amount: String,
+    },
+    ApproveBuilderFee {
+        builder: String,
+        max_fee_tenths_bps: u32,
+    },
+}
+
+/// A built action together with the digest a signer has to sign.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct UnsignedAction {
+    pub action_json: String,
+    pub nonce: u64,
+    pub vault_address: Option<String>,
+    pub is_mainnet: bool,
+    pub digest: String,
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct SignedAction {
+    pub action_json: String,
+    pub nonce: u64,
+    pub vault_address: Option<String>,
+    pub is_mainnet: bool,
+    pub signer_address: String,
+    pub signature: String,
+    /// Body ready to POST to /exchange
+    pub payload_json: String,
+}
+
+/// Builds an action and its signing digest; `nonce` defaults to the current time in ms.
+#[uniffi::export]
+pub fn build_action(
+    request: ActionRequest,
+    nonce: Option<u64>,
+    vault_address: Option<String>,
+    is_mainnet: bool,
+) -> Result<UnsignedAction, SignerError> {
+    let nonce = nonce.unwrap_or_else(next_nonce);
+    let action = action_from_request(request, nonce, is_mainnet)?;
+    let vault = parse_vault_address(&action, vault_address.as_deref())?;
+    let digest = action
+        .signing_digest(nonce, vault, is_mainnet)
+        .map_err(|e| invalid_action(format!("{}", e)))?;
+
+    Ok(UnsignedAction {
+        action_json: serde_json::to_string(&action).map_err(|e| invalid_action(format!("{}", e)))?,
+        nonce,
+        vault_address,
+        is_mainnet,
+        digest: format!("{:?}", digest),
+    })
+}
+
+/// Signs a built action without sending it anywhere.
+#[uniffi::export]
+pub fn sign_action(action: UnsignedAction, signer: Arc<dyn Signer>) -> Result<SignedAction, SignerError> {
+    let parsed = parse_action_json(&action.action_json)?;
+    let vault = parse_vault_address(&parsed, action.vault_address.as_deref())?;
+    let payload = sign_payload(&parsed, action.nonce, vault, action.is_mainnet, signer.as_ref())
+        .map_err(|e| SignerError::SigningFailed { reason: format!("{}", e) })?;
+
+    Ok(SignedAction {
+        action_json: action.action_json,
+        nonce: action.nonce,
+        vault_address: action.vault_address,
+        is_mainnet: action.is_mainnet,
+        signer_address: signer.address(),
+        signature: payload.signature.to_string(),
+        payload_json: payload_json(&payload)?,
+    })
+}
+
+/// Recomputes the digest from the action itself and checks the signature recovers to `expected_address`.
+#[uniffi::export]
+pub fn verify_action_signature(action: SignedAction, expected_address: String) -> Result<bool, SignerError> {
+    let expected: H160 = expected_address
+        .parse()
+        .map_err(|_| invalid_action(format!("Invalid address: {}", expected_address)))?;
+    let parsed = parse_action_json(&action.action_json)?;
+    let vault = parse_vault_address(&parsed, action.vault_address.as_deref())?;
+    let digest = parsed
+        .signing_digest(action.nonce, vault, action.is_mainnet)
+        .map_err(|e| invalid_action(format!("{}", e)))?;
+
+    let bytes = hex::decode(action.signature.trim_start_matches("0x"))
+        .map_err(|e| invalid_action(format!("Invalid signature hex: {}", e)))?;
+    let signature = Signature::try_from(bytes.as_slice()).map_err(|e| invalid_action(format!("Malformed signature: {}", e)))?;
+
+    Ok(signature.recover(digest).map(|recovered| recovered == expected).unwrap_or(false))
+}
+
+fn action_from_request(request: ActionRequest, nonce: u64, is_mainnet: bool) -> Result<Action, SignerError> {
+    let action = match request {
+        ActionRequest::Order { asset, is_buy, limit_price, size, reduce_only, time_in_force, cloid, builder } => {
+            check_decimal("limit price", &limit_price)?;
+            check_decimal("size", &size)?;
+            let builder = builder
+                .map(|builder| {
+                    check_builder_fee(builder.fee_tenths_bps, asset >= SPOT_ASSET_OFFSET)?;
+                    Ok::<_, SignerError>(BuilderInfo {
+                        builder: parse_address("builder", &builder.address)?,
+                        fee: builder.fee_tenths_bps as u64,
+                    })
+                })
+                .transpose()?;
+            Action::Order(BulkOrder {
+                orders: vec![OrderWire {
+                    asset,
+                    is_buy,
+                    limit_px: limit_price,
+                    sz: size,
+                    reduce_only,
+                    order_type: OrderTypeWire::Limit { tif: time_in_force },
+                    cloid,
+                }],
+                grouping: "na".to_string(),
+                builder,
+            })
+        }
+        ActionRequest::Cancel { asset, order_id } => Action::Cancel(BulkCancel {
+            cancels: vec![CancelWire { asset, oid: order_id }],
+        }),
+        ActionRequest::UsdSend { destination, amount } => {
+            check_decimal("amount", &amount)?;
+            Action::UsdSend(UsdSend {
+                signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+                hyperliquid_chain: hyperliquid_chain(is_mainnet),
+                destination,
+                amount,
+                time: nonce,
+            })
+        }
+        ActionRequest::SpotSend { destination, token, amount } => {
+            check_decimal("amount", &amount)?;
+            Action::SpotSend(SpotSend {
+                hyperliquid_chain: hyperliquid_chain(is_mainnet),
+                signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+                destination,
+                token,
+                amount,
+                time: nonce,
+            })
+        }
+        ActionRequest::Withdraw { destination, amount } => {
+            check_decimal("amount", &amount)?;
+            Action::Withdraw3(Withdraw3 {
+                hyperliquid_chain: hyperliquid_chain(is_mainnet),
+                signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+                amount,
+                time: nonce,
+                destination,
+            })
+        }
+        ActionRequest::VaultTransfer { vault_address, is_deposit, usd } => Action::VaultTransfer(VaultTransfer {
+            vault_address: vault_address.to_lowercase(),
+            is_deposit,
+            usd,
+        }),
+        ActionRequest::CreateSubAccount { name } => Action::CreateSubAccount(CreateSubAccount { name }),
+        ActionRequest::SubAccountTransfer { sub_account, is_deposit, usd } => {
+            Action::SubAccountTransfer(SubAccountTransfer {
+                sub_account_user: sub_account.to_lowercase(),
+                is_deposit,
+                usd,
+            })
+        }
+        ActionRequest::SubAccountSpotTransfer { sub_account, is_deposit, token, amount } => {
+            check_decimal("amount", &amount)?;
+            Action::SubAccountSpotTransfer(SubAccountSpotTransfer {
+                sub_account_user: sub_account.to_lowercase(),
+                is_deposit,
+                token,
+                amount,
+            })
+        }
+        ActionRequest::ApproveBuilderFee { builder, max_fee_tenths_bps } => {
+            // An approval covers spot orders too, so it may go up to the spot cap
+            check_builder_fee(max_fee_tenths_bps, true)?;
+            Action::ApproveBuilderFee(ApproveBuilderFee {
+                signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+                hyperliquid_chain: hyperliquid_chain(is_mainnet),
+                max_fee_rate: builder_fee_rate(max_fee_tenths_bps),
+                builder: parse_address("builder", &builder)?,
+                nonce,
+            })
+        }
+    };
+    Ok(action)
+}
+
+fn parse_action_json(json: &str) -> Result<Action, SignerError> {
+    serde_json::from_str(json).map_err(|e| invalid_action(format!("Unsupported action JSON: {}", e)))
+}
+
+// User-signed transfers commit to their own fields only, a vault cannot act for them
+fn parse_vault_address(action: &Action, vault_address: Option<&str>) -> Result<Option<H160>, SignerError> {
+    let Some(vault_address) = vault_address else {
+        return Ok(None);
+    };
+    if action.is_user_signed() {
+        return Err(invalid_action("Transfers cannot be signed on behalf of a vault".to_string()));
+    }
+    vault_address
+        .parse()
+        .map(Some)
+        .map_err(|_| invalid_action(format!("Invalid vault address: {}", vault_address)))
+}
+
+fn check_decimal(field: &str, value: &str) -> Result<(), SignerError> {
+    match value.parse::<f64>() {
+        Ok(parsed) if parsed.is_finite() && !value.contains(['e', 'E']) => Ok(()),
+        _ => Err(invalid_action(format!("Invalid {}: {}", field, value))),
+    }
+}
+
+fn check_builder_fee(fee_tenths_bps: u32, is_spot: bool) -> Result<(), SignerError> {
+    if fee_tenths_bps > max_builder_fee_tenths_bps(is_spot) {
+        let cap = if is_spot { "1%" } else { "0.1% on perps" };
+        return Err(invalid_action(format!("Builder fee above {}: {} tenths of a bp", cap, fee_tenths_bps)));
+    }
+    Ok(())
+}
+
+// Addresses inside actions are signed in lowercase
+fn parse_address(field: &str, address: &str) -> Result<String, SignerError> {
+    let parsed: H160 = address.parse().map_err(|_| invalid_action(format!("Invalid {} address: {}", field, address)))?;
+    Ok(format!("{:#x}", parsed))
+}
+
+fn payload_json(payload: &ExchangePayload) -> Result<String, SignerError> {
+    serde_json::to_string(payload).map_err(|e| invalid_action(format!("{}", e)))
+}
+
+fn invalid_action(reason: String) -> SignerError {
+    SignerError::InvalidAction { reason }
+}
@@ -0,0 +1,291 @@
+use crate::{CandleData, UserFill};
+use rusqlite::{params, Connection, Row};
+use std::sync::Mutex;
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum CacheError {
+    #[error("Cache database error: {reason}")]
+    Database { reason: String },
+}
+
+impl From<rusqlite::Error> for CacheError {
+    fn from(error: rusqlite::Error) -> Self {
+        CacheError::Database { reason: error.to_string() }
+    }
+}
+
+// Candles and fills can have legitimate holes, so what has already been asked
+// of the API is tracked separately from the rows themselves
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS candles (
+        coin TEXT NOT NULL,
+        interval TEXT NOT NULL,
+        time_open INTEGER NOT NULL,
+        time_close INTEGER NOT NULL,
+        open TEXT NOT NULL,
+        close TEXT NOT NULL,
+        high TEXT NOT NULL,
+        low TEXT NOT NULL,
+        volume TEXT NOT NULL,
+        num_trades INTEGER NOT NULL,
+        PRIMARY KEY (coin, interval, time_open)
+    );
+    CREATE TABLE IF NOT EXISTS fills (
+        address TEXT NOT NULL,
+        fill_key TEXT NOT NULL,
+        time INTEGER NOT NULL,
+        coin TEXT NOT NULL,
+        px TEXT NOT NULL,
+        sz TEXT NOT NULL,
+        side TEXT NOT NULL,
+        start_position TEXT NOT NULL,
+        dir TEXT NOT NULL,
+        closed_pnl TEXT NOT NULL,
+        hash TEXT NOT NULL,
+        oid INTEGER NOT NULL,
+        crossed INTEGER NOT NULL,
+        fee TEXT,
+        tid INTEGER,
+        fee_token TEXT,
+        PRIMARY KEY (address, fill_key)
+    );
+    CREATE INDEX IF NOT EXISTS fills_by_time ON fills (address, time);
+    CREATE TABLE IF NOT EXISTS fetched_ranges (
+        kind TEXT NOT NULL,
+        key TEXT NOT NULL,
+        start_time INTEGER NOT NULL,
+        end_time INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS fetched_ranges_by_key ON fetched_ranges (kind, key, start_time);
+";
+
+/// On-device SQLite cache of candles and fills.
+// Crate-private through its module; `pub` so the test-hooks feature can export it
+pub struct Store {
+    connection: Mutex<Connection>,
+}
+
+impl Store {
+    pub fn open(path: &str) -> Result<Self, CacheError> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn clear(&self) -> Result<(), CacheError> {
+        let connection = self.connection.lock().unwrap();
+        connection.execute_batch("DELETE FROM candles; DELETE FROM fills; DELETE FROM fetched_ranges;")?;
+        Ok(())
+    }
+
+    /// Parts of [start, end] not fetched yet, as inclusive ranges.
+    pub fn missing_ranges(&self, kind: &str, key: &str, start_time: u64, end_time: u64) -> Result<Vec<(u64, u64)>, CacheError> {
+        let end_time = end_time.min(MAX_TIME);
+        if start_time > end_time {
+            return Ok(Vec::new());
+        }
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT start_time, end_time FROM fetched_ranges
+             WHERE kind = ?1 AND key = ?2 AND end_time >= ?3 AND start_time <= ?4
+             ORDER BY start_time",
+        )?;
+        let fetched = statement
+            .query_map(params![kind, key, sql_int(start_time), sql_int(end_time)], |row| {
+                Ok((row.get::<_, i64>(0)? as u64, row.get::<_, i64>(1)? as u64))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut missing = Vec::new();
+        let mut cursor = start_time;
+        for (fetched_start, fetched_end) in fetched {
+            if fetched_start > cursor {
+                missing.push((cursor, fetched_start - 1));
+            }
+            cursor = cursor.max(fetched_end.saturating_add(1));
+        }
+        if cursor <= end_time {
+            missing.push((cursor, end_time));
+        }
+        Ok(missing)
+    }
+
+    /// Records [start, end] as fetched, merged with the ranges it touches.
+    pub fn add_range(&self, kind: &str, key: &str, start_time: u64, end_time: u64) -> Result<(), CacheError> {
+        let end_time = end_time.min(MAX_TIME);
+        if start_time > end_time {
+            return Ok(());
+        }
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        let (merged_start, merged_end): (i64, i64) = transaction.query_row(
+            "SELECT MIN(start_time, ?3), MAX(end_time, ?4) FROM (
+                 SELECT COALESCE(MIN(start_time), ?3) AS start_time, COALESCE(MAX(end_time), ?4) AS end_time
+                 FROM fetched_ranges WHERE kind = ?1 AND key = ?2 AND end_time >= ?3 - 1 AND start_time <= ?4 + 1
+             )",
+            params![kind, key, sql_int(start_time), sql_int(end_time)],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        transaction.execute(
+            "DELETE FROM fetched_ranges WHERE kind = ?1 AND key = ?2 AND end_time >= ?3 - 1 AND start_time <= ?4 + 1",
+            params![kind, key, sql_int(start_time), sql_int(end_time)],
+        )?;
+        transaction.execute(
+            "INSERT INTO fetched_ranges (kind, key, start_time, end_time) VALUES (?1, ?2, ?3, ?4)",
+            params![kind, key, merged_start, merged_end],
+        )?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Records the part of [start, end] up to `settled_until`; anything later can
+    /// still change, so it stays missing and is fetched again next time.
+    pub fn add_settled_range(&self, kind: &str, key: &str, start_time: u64, end_time: u64, settled_until: u64) -> Result<(), CacheError> {
+        let end_time = end_time.min(settled_until);
+        if end_time < start_time {
+            return Ok(());
+        }
+        self.add_range(kind, key, start_time, end_time)
+    }
+
+    // Stored under the coin and interval they were requested as
+    pub(crate) fn put_candles(&self, coin: &str, interval: &str, candles: &[CandleData]) -> Result<(), CacheError> {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR REPLACE INTO candles
+                 (coin, interval, time_open, time_close, open, close, high, low, volume, num_trades)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            for candle in candles {
+                statement.execute(params![
+                    coin,
+                    interval,
+                    sql_int(candle.time_open),
+                    sql_int(candle.time_close),
+                    candle.open,
+                    candle.close,
+                    candle.high,
+                    candle.low,
+                    candle.volume,
+                    sql_int(candle.num_trades),
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    pub(crate) fn candles(&self, coin: &str, interval: &str, start_time: u64, end_time: u64) -> Result<Vec<CandleData>, CacheError> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT coin, interval, time_open, time_close, open, close, high, low, volume, num_trades FROM candles
+             WHERE coin = ?1 AND interval = ?2 AND time_open BETWEEN ?3 AND ?4
+             ORDER BY time_open",
+        )?;
+        let candles = statement
+            .query_map(params![coin, interval, sql_int(start_time), sql_int(end_time)], candle_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(candles)
+    }
+
+    pub(crate) fn put_fills(&self, address: &str, fills: &[UserFill]) -> Result<(), CacheError> {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR REPLACE INTO fills
+                 (address, fill_key, time, coin, px, sz, side, start_position, dir, closed_pnl, hash, oid, crossed, fee, tid, fee_token)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            )?;
+            for fill in fills {
+                statement.execute(params![
+                    address,
+                    fill_key(fill),
+                    sql_int(fill.time),
+                    fill.coin,
+                    fill.px,
+                    fill.sz,
+                    fill.side,
+                    fill.start_position,
+                    fill.dir,
+                    fill.closed_pnl,
+                    fill.hash,
+                    sql_int(fill.oid),
+                    fill.crossed,
+                    fill.fee,
+                    fill.tid.map(sql_int),
+                    fill.fee_token,
+                ])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    pub(crate) fn fills(&self, address: &str, start_time: u64, end_time: u64) -> Result<Vec<UserFill>, CacheError> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT coin, px, sz, side, time, start_position, dir, closed_pnl, hash, oid, crossed, fee, tid, fee_token FROM fills
+             WHERE address = ?1 AND time BETWEEN ?2 AND ?3
+             ORDER BY time, tid",
+        )?;
+        let fills = statement
+            .query_map(params![address, sql_int(start_time), sql_int(end_time)], fill_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(fills)
+    }
+}
+
+// SQLite integers are signed, and ranges are compared against end + 1, so
+// larger values saturate; nothing is ever stored that late
+const MAX_TIME: u64 = i64::MAX as u64 - 1;
+
+fn sql_int(value: u64) -> i64 {
+    value.min(MAX_TIME) as i64
+}
+
+// The trade id identifies a fill; older fills without one fall back to order and time
+pub(crate) fn fill_key(fill: &UserFill) -> String {
+    match fill.tid {
+        Some(tid) => tid.to_string(),
+        None => format!("{}:{}:{}", fill.hash, fill.oid, fill.time),
+    }
+}
+
+fn candle_from_row(row: &Row) -> rusqlite::Result<CandleData> {
+    Ok(CandleData {
+        coin: row.get(0)?,
+        interval: row.get(1)?,
+        time_open: row.get::<_, i64>(2)? as u64,
+        time_close: row.get::<_, i64>(3)? as u64,
+        open: row.get(4)?,
+        close: row.get(5)?,
+        high: row.get(6)?,
+        low: row.get(7)?,
+        volume: row.get(8)?,
+        num_trades: row.get::<_, i64>(9)? as u64,
+    })
+}
+
+fn fill_from_row(row: &Row) -> rusqlite::Result<UserFill> {
+    Ok(UserFill {
+        coin: row.get(0)?,
+        px: row.get(1)?,
+        sz: row.get(2)?,
+        side: row.get(3)?,
+        time: row.get::<_, i64>(4)? as u64,
+        start_position: row.get(5)?,
+        dir: row.get(6)?,
+        closed_pnl: row.get(7)?,
+        hash: row.get(8)?,
+        oid: row.get::<_, i64>(9)? as u64,
+        crossed: row.get(10)?,
+        fee: row.get(11)?,
+        tid: row.get::<_, Option<i64>>(12)?.map(|tid| tid as u64),
+        fee_token: row.get(13)?,
+    })
+}
@@ -0,0 +1,445 @@
+use crate::pnl::{CostBasisMethod, Ledger, QUOTE_TOKEN};
+use crate::{number, UserFill};
+use chrono::{DateTime, Months, NaiveDate, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+// Every row carries both the millisecond timestamp and an ISO 8601 UTC date,
+// so spreadsheets and scripts can each use the one they read best. Amounts
+// are USD, treating USDC as a dollar.
+
+/// One fill within the report year.
+#[derive(uniffi::Record, Serialize, Debug, Clone)]
+pub struct TaxTrade {
+    pub time: u64,
+    pub date: String,
+    pub coin: String,
+    // "Buy" or "Sell"
+    pub side: String,
+    // As the exchange describes it, e.g. "Open Long"
+    pub direction: String,
+    pub size: f64,
+    pub price: f64,
+    pub notional: f64,
+    pub fee: f64,
+    pub fee_token: String,
+    // None when the fee token had no USD price at the time
+    pub fee_usd: Option<f64>,
+    // Exchange-reported, on its average cost basis
+    pub closed_pnl: f64,
+    pub hash: String,
+    pub tid: Option<u64>,
+}
+
+/// A FIFO lot, or part of one, closed within the report year.
+#[derive(uniffi::Record, Serialize, Debug, Clone)]
+pub struct TaxLot {
+    pub coin: String,
+    pub size: f64,
+    // Sold first and bought back later; acquisition is then the opening sale
+    pub is_short: bool,
+    pub acquired_time: u64,
+    pub acquired_date: String,
+    pub disposed_time: u64,
+    pub disposed_date: String,
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    // Before fees, which the trade rows list separately
+    pub gain: f64,
+    pub holding_days: u32,
+    // Held for more than a year
+    pub long_term: bool,
+    // False when the pair's quote token had no USD price; proceeds, cost
+    // basis and gain are then in the quote token
+    pub usd_priced: bool,
+}
+
+/// A funding payment; positive when received.
+#[derive(uniffi::Record, Serialize, Debug, Clone)]
+pub struct FundingPayment {
+    pub time: u64,
+    pub date: String,
+    pub coin: String,
+    pub usdc: f64,
+    pub position_size: f64,
+    pub funding_rate: f64,
+    pub hash: String,
+}
+
+/// A non-funding ledger update: deposits, withdrawals, transfers, vault flows
+/// and liquidations.
+#[derive(uniffi::Record, Serialize, Debug, Clone)]
+pub struct LedgerEntry {
+    pub time: u64,
+    pub date: String,
+    // The update's type, e.g. "deposit" or "spotTransfer"
+    pub kind: String,
+    pub token: String,
+    // Negative when leaving the account
+    pub amount: f64,
+    pub usd_value: Option<f64>,
+    pub fee: f64,
+    pub hash: String,
+    // The update as the API returned it
+    pub details: String,
+}
+
+#[derive(uniffi::Record, Serialize, Debug, Clone)]
+pub struct TaxSummary {
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub realized_gain: f64,
+    pub short_term_gain: f64,
+    pub long_term_gain: f64,
+    pub trading_fees_usd: f64,
+    pub funding_net: f64,
+    pub trade_count: u32,
+}
+
+#[derive(uniffi::Record, Serialize, Debug, Clone)]
+pub struct TaxReport {
+    pub address: String,
+    pub year: i32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub trades: Vec<TaxTrade>,
+    pub lots: Vec<TaxLot>,
+    pub funding: Vec<FundingPayment>,
+    pub ledger: Vec<LedgerEntry>,
+    pub summary: TaxSummary,
+    // Some coin's fills began with a position already open, so lots opened
+    // before them are costed at the first fill's price
+    pub incomplete_history: bool,
+}
+
+/// Table of a report to export as CSV.
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxReportSection {
+    Trades,
+    Lots,
+    Funding,
+    Ledger,
+}
+
+/// First and last millisecond of a UTC calendar year.
+pub(crate) fn year_bounds(year: i32) -> (u64, u64) {
+    let start_of = |year: i32| {
+        NaiveDate::from_ymd_opt(year, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map_or(0, |date| date.and_utc().timestamp_millis().max(0) as u64)
+    };
+    (start_of(year), start_of(year + 1).saturating_sub(1))
+}
+
+pub(crate) fn format_date(time: u64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(time as i64)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+pub(crate) fn parse_funding_payment(update: &serde_json::Value) -> FundingPayment {
+    let delta = &update["delta"];
+    let time = update["time"].as_u64().unwrap_or(0);
+    FundingPayment {
+        time,
+        date: format_date(time),
+        coin: delta["coin"].as_str().unwrap_or("").to_string(),
+        usdc: number_field(&delta["usdc"]).unwrap_or(0.0),
+        position_size: number_field(&delta["szi"]).unwrap_or(0.0),
+        funding_rate: number_field(&delta["fundingRate"]).unwrap_or(0.0),
+        hash: update["hash"].as_str().unwrap_or("").to_string(),
+    }
+}
+
+// Each kind of update names its amount differently, and most report it
+// unsigned, so the direction comes from the kind or the transfer's parties
+pub(crate) fn parse_ledger_entry(update: &serde_json::Value, address: &str) -> LedgerEntry {
+    let delta = &update["delta"];
+    let time = update["time"].as_u64().unwrap_or(0);
+    let kind = delta["type"].as_str().unwrap_or("").to_string();
+    let amount = ["usdc", "amount", "netWithdrawnUsd", "requestedUsd"]
+        .iter()
+        .find_map(|field| number_field(&delta[*field]))
+        .unwrap_or(0.0);
+    let is_address = |field: &str| delta[field].as_str().is_some_and(|party| party.eq_ignore_ascii_case(address));
+    let outgoing = match kind.as_str() {
+        "withdraw" | "vaultDeposit" => true,
+        "deposit" | "vaultWithdraw" | "accountClassTransfer" => false,
+        _ => is_address("user") && !is_address("destination"),
+    };
+    let amount = if outgoing { -amount.abs() } else { amount };
+    let token = delta["token"].as_str().unwrap_or(QUOTE_TOKEN).to_string();
+    let usd_value = number_field(&delta["usdcValue"]).or((token == QUOTE_TOKEN).then_some(amount));
+    LedgerEntry {
+        time,
+        date: format_date(time),
+        kind,
+        token,
+        amount,
+        usd_value,
+        fee: number_field(&delta["fee"]).unwrap_or(0.0),
+        hash: update["hash"].as_str().unwrap_or("").to_string(),
+        details: delta.to_string(),
+    }
+}
+
+/// Builds a report for a calendar year (UTC) from an address's fills, funding
+/// payments and ledger updates.
+///
+/// `fills` should reach back as far as possible, since lots disposed of in
+/// the year may have been acquired before it. Fees charged in USDC count at
+/// face value and fees in any other token (spot buys) at the fill price.
+/// Lots of "BASE/QUOTE" pairs quoted in another token are left unpriced;
+/// `get_tax_report` prices those and fees in other quote tokens.
+#[uniffi::export]
+pub fn build_tax_report(address: String, year: i32, fills: Vec<UserFill>, funding: Vec<FundingPayment>, ledger: Vec<LedgerEntry>) -> TaxReport {
+    build_report(
+        address,
+        year,
+        fills,
+        funding,
+        ledger,
+        |fill, fee| match fill.fee_token.as_deref() {
+            None | Some(QUOTE_TOKEN) => Some(fee),
+            Some(_) => Some(fee * number(&fill.px)),
+        },
+        |coin, _| match coin.split_once('/') {
+            Some((_, quote)) if quote != QUOTE_TOKEN => None,
+            _ => Some(1.0),
+        },
+    )
+}
+
+/// Like `build_tax_report`, with `fee_usd` valuing a fill's fee in USD and
+/// `quote_usd` one unit of a coin's quote token at a given time.
+pub(crate) fn build_report(
+    address: String,
+    year: i32,
+    mut fills: Vec<UserFill>,
+    funding: Vec<FundingPayment>,
+    ledger: Vec<LedgerEntry>,
+    mut fee_usd: impl FnMut(&UserFill, f64) -> Option<f64>,
+    mut quote_usd: impl FnMut(&str, u64) -> Option<f64>,
+) -> TaxReport {
+    let (start_time, end_time) = year_bounds(year);
+    let in_year = |time: u64| time >= start_time && time <= end_time;
+    fills.sort_by_key(|fill| (fill.time, fill.tid.unwrap_or(0)));
+
+    let mut ledgers: HashMap<String, Ledger> = HashMap::new();
+    let mut trades = Vec::new();
+    let mut lots = Vec::new();
+    for fill in fills.iter().filter(|fill| fill.time <= end_time) {
+        let coin_ledger = ledgers.entry(fill.coin.clone()).or_insert_with(|| Ledger::new(CostBasisMethod::Fifo));
+        let disposals = coin_ledger.apply_fill(fill);
+        if !in_year(fill.time) {
+            continue;
+        }
+        for disposal in disposals {
+            let size = disposal.size.abs();
+            let is_short = disposal.size < 0.0;
+            // Each side is valued at the quote token's price when it traded
+            let rates = (quote_usd(&fill.coin, disposal.opened), quote_usd(&fill.coin, disposal.closed));
+            let (entry_rate, exit_rate, usd_priced) = match rates {
+                (Some(entry_rate), Some(exit_rate)) => (entry_rate, exit_rate, true),
+                _ => (1.0, 1.0, false),
+            };
+            let entry_value = size * disposal.entry_price * entry_rate;
+            let exit_value = size * disposal.exit_price * exit_rate;
+            let (proceeds, cost_basis) = if is_short { (entry_value, exit_value) } else { (exit_value, entry_value) };
+            lots.push(TaxLot {
+                coin: fill.coin.clone(),
+                size,
+                is_short,
+                acquired_time: disposal.opened,
+                acquired_date: format_date(disposal.opened),
+                disposed_time: disposal.closed,
+                disposed_date: format_date(disposal.closed),
+                proceeds,
+                cost_basis,
+                gain: proceeds - cost_basis,
+                holding_days: (disposal.closed.saturating_sub(disposal.opened) / DAY_MS) as u32,
+                long_term: held_over_a_year(disposal.opened, disposal.closed),
+                usd_priced,
+            });
+        }
+
+        let size = number(&fill.sz);
+        let price = number(&fill.px);
+        let fee = fill.fee.as_deref().map_or(0.0, number);
+        trades.push(TaxTrade {
+            time: fill.time,
+            date: format_date(fill.time),
+            coin: fill.coin.clone(),
+            side: if fill.side == "B" { "Buy" } else { "Sell" }.to_string(),
+            direction: fill.dir.clone(),
+            size,
+            price,
+            notional: size * price,
+            fee,
+            fee_token: fill.fee_token.clone().unwrap_or_else(|| QUOTE_TOKEN.to_string()),
+            fee_usd: fee_usd(fill, fee),
+            closed_pnl: number(&fill.closed_pnl),
+            hash: fill.hash.clone(),
+            tid: fill.tid,
+        });
+    }
+
+    let funding: Vec<FundingPayment> = funding.into_iter().filter(|payment| in_year(payment.time)).collect();
+    let ledger: Vec<LedgerEntry> = ledger.into_iter().filter(|entry| in_year(entry.time)).collect();
+
+    let summary = TaxSummary {
+        proceeds: lots.iter().map(|lot| lot.proceeds).sum(),
+        cost_basis: lots.iter().map(|lot| lot.cost_basis).sum(),
+        realized_gain: lots.iter().map(|lot| lot.gain).sum(),
+        short_term_gain: lots.iter().filter(|lot| !lot.long_term).map(|lot| lot.gain).sum(),
+        long_term_gain: lots.iter().filter(|lot| lot.long_term).map(|lot| lot.gain).sum(),
+        trading_fees_usd: trades.iter().filter_map(|trade| trade.fee_usd).sum(),
+        funding_net: funding.iter().map(|payment| payment.usdc).sum(),
+        trade_count: trades.len() as u32,
+    };
+    let incomplete_history = ledgers.values().any(|ledger| ledger.incomplete_history());
+
+    TaxReport {
+        address,
+        year,
+        start_time,
+        end_time,
+        trades,
+        lots,
+        funding,
+        ledger,
+        summary,
+        incomplete_history,
+    }
+}
+
+fn held_over_a_year(acquired: u64, disposed: u64) -> bool {
+    let acquired = DateTime::<Utc>::from_timestamp_millis(acquired as i64).unwrap_or_default();
+    acquired
+        .checked_add_months(Months::new(12))
+        .is_some_and(|anniversary| disposed as i64 > anniversary.timestamp_millis())
+}
+
+/// The whole report as pretty-printed JSON.
+#[uniffi::export]
+pub fn tax_report_json(report: TaxReport) -> String {
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+/// One table of a report as CSV, with a header row.
+#[uniffi::export]
+pub fn tax_report_csv(report: TaxReport, section: TaxReportSection) -> String {
+    let optional = |value: Option<f64>| value.map_or(String::new(), |value| value.to_string());
+    let (header, rows): (&[&str], Vec<Vec<String>>) = match section {
+        TaxReportSection::Trades => (
+            &["time", "date", "coin", "side", "direction", "size", "price", "notional", "fee", "fee_token", "fee_usd", "closed_pnl", "hash", "tid"],
+            report
+                .trades
+                .iter()
+                .map(|trade| {
+                    vec![
+                        trade.time.to_string(),
+                        trade.date.clone(),
+                        trade.coin.clone(),
+                        trade.side.clone(),
+                        trade.direction.clone(),
+                        trade.size.to_string(),
+                        trade.price.to_string(),
+                        trade.notional.to_string(),
+                        trade.fee.to_string(),
+                        trade.fee_token.clone(),
+                        optional(trade.fee_usd),
+                        trade.closed_pnl.to_string(),
+                        trade.hash.clone(),
+                        trade.tid.map_or(String::new(), |tid| tid.to_string()),
+                    ]
+                })
+                .collect(),
+        ),
+        TaxReportSection::Lots => (
+            &["coin", "size", "is_short", "acquired_time", "acquired_date", "disposed_time", "disposed_date", "proceeds", "cost_basis", "gain", "holding_days", "long_term", "usd_priced"],
+            report
+                .lots
+                .iter()
+                .map(|lot| {
+                    vec![
+                        lot.coin.clone(),
+                        lot.size.to_string(),
+                        lot.is_short.to_string(),
+                        lot.acquired_time.to_string(),
+                        lot.acquired_date.clone(),
+                        lot.disposed_time.to_string(),
+                        lot.disposed_date.clone(),
+                        lot.proceeds.to_string(),
+                        lot.cost_basis.to_string(),
+                        lot.gain.to_string(),
+                        lot.holding_days.to_string(),
+                        lot.long_term.to_string(),
+                        lot.usd_priced.to_string(),
+                    ]
+                })
+                .collect(),
+        ),
+        TaxReportSection::Funding => (
+            &["time", "date", "coin", "usdc", "position_size", "funding_rate", "hash"],
+            report
+                .funding
+                .iter()
+                .map(|payment| {
+                    vec![
+                        payment.time.to_string(),
+                        payment.date.clone(),
+                        payment.coin.clone(),
+                        payment.usdc.to_string(),
+                        payment.position_size.to_string(),
+                        payment.funding_rate.to_string(),
+                        payment.hash.clone(),
+                    ]
+                })
+                .collect(),
+        ),
+        TaxReportSection::Ledger => (
+            &["time", "date", "kind", "token", "amount", "usd_value", "fee", "hash", "details"],
+            report
+                .ledger
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.time.to_string(),
+                        entry.date.clone(),
+                        entry.kind.clone(),
+                        entry.token.clone(),
+                        entry.amount.to_string(),
+                        optional(entry.usd_value),
+                        entry.fee.to_string(),
+                        entry.hash.clone(),
+                        entry.details.clone(),
+                    ]
+                })
+                .collect(),
+        ),
+    };
+
+    let mut csv = String::new();
+    for row in std::iter::once(header.iter().map(|field| field.to_string()).collect()).chain(rows) {
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+// Quoted only when needed, doubling any quotes inside (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn number_field(value: &serde_json::Value) -> Option<f64> {
+    value.as_str().and_then(|value| value.parse().ok()).or_else(|| value.as_f64())
+}
@@ -0,0 +1,264 @@
+use hyperliquid_sdk_swift::{build_action, new_local_signer, sign_action, verify_action_signature, ActionRequest, BuilderFee, HyperliquidClient, Signer};
+use std::sync::Arc;
+
+// Known-good signatures produced by the official Python and Rust SDKs for this key
+const PRIVATE_KEY: &str = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+const NONCE: u64 = 1583838;
+const TRANSFER_TIME: u64 = 1690393044548;
+const DESTINATION: &str = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414";
+
+fn limit_order(cloid: Option<&str>) -> ActionRequest {
+    ActionRequest::Order {
+        asset: 1,
+        is_buy: true,
+        limit_price: "2000.0".to_string(),
+        size: "3.5".to_string(),
+        reduce_only: false,
+        time_in_force: "Ioc".to_string(),
+        cloid: cloid.map(|c| c.to_string()),
+        builder: None,
+    }
+}
+
+// Builds each request, compares its action JSON and checks the signature round trips
+fn check_actions<const N: usize>(cases: [(ActionRequest, Option<String>, &str); N], signer: &Arc<dyn Signer>, address: &str) -> u32 {
+    let mut failures = 0;
+    for (request, vault_address, expected) in cases {
+        let signed = build_action(request, Some(NONCE), vault_address, true).and_then(|action| sign_action(action, signer.clone()));
+        match signed {
+            Ok(signed) if signed.action_json == expected => match verify_action_signature(signed, address.to_string()) {
+                Ok(true) => println!("  ✅ {}", expected),
+                other => {
+                    println!("  ❌ Verification failed: {:?}", other);
+                    failures += 1;
+                }
+            },
+            Ok(signed) => {
+                println!("  ❌ Got {}", signed.action_json);
+                failures += 1;
+            }
+            Err(e) => {
+                println!("  ❌ Building failed: {}", e);
+                failures += 1;
+            }
+        }
+    }
+    failures
+}
+
+fn main() {
+    println!("✍️ [DEBUG] Testing offline action signing against golden vectors");
+    println!("==============================================================");
+
+    let signer = new_local_signer(PRIVATE_KEY.to_string()).expect("Invalid test key");
+    let address = signer.address();
+    println!("  • Signer: {}", address);
+
+    let vectors = [
+        ("limit order (mainnet)", limit_order(None), NONCE, true, "77957e58e70f43b6b68581f2dc42011fc384538a2e5b7bf42d5b936f19fbb67360721a8598727230f67080efee48c812a6a4442013fd3b0eed509171bef9f23f1c"),
+        ("limit order (testnet)", limit_order(None), NONCE, false, "cd0925372ff1ed499e54883e9a6205ecfadec748f80ec463fe2f84f1209648776377961965cb7b12414186b1ea291e95fd512722427efcbcfb3b0b2bcd4d79d01c"),
+        ("limit order with cloid (mainnet)", limit_order(Some("0x1e60610f0b3d420597c88c1fed2ad5ee")), NONCE, true, "d3e894092eb27098077145714630a77bbe3836120ee29df7d935d8510b03a08f456de5ec1be82aa65fc6ecda9ef928b0445e212517a98858cfaa251c4cd7552b1c"),
+        ("limit order with cloid (testnet)", limit_order(Some("0x1e60610f0b3d420597c88c1fed2ad5ee")), NONCE, false, "3768349dbb22a7fd770fc9fc50c7b5124a7da342ea579b309f58002ceae49b4357badc7909770919c45d850aabb08474ff2b7b3204ae5b66d9f7375582981f111c"),
+        ("cancel (mainnet)", ActionRequest::Cancel { asset: 1, order_id: 82382 }, NONCE, true, "02f76cc5b16e0810152fa0e14e7b219f49c361e3325f771544c6f54e157bf9fa17ed0afc11a98596be85d5cd9f86600aad515337318f7ab346e5ccc1b03425d51b"),
+        ("cancel (testnet)", ActionRequest::Cancel { asset: 1, order_id: 82382 }, NONCE, false, "6ffebadfd48067663390962539fbde76cfa36f53be65abe2ab72c9db6d0db44457720db9d7c4860f142a484f070c84eb4b9694c3a617c83f0d698a27e55fd5e01c"),
+        ("usd send (testnet)", ActionRequest::UsdSend { destination: DESTINATION.to_string(), amount: "1".to_string() }, TRANSFER_TIME, false, "214d507bbdaebba52fa60928f904a8b2df73673e3baba6133d66fe846c7ef70451e82453a6d8db124e7ed6e60fa00d4b7c46e4d96cb2bd61fd81b6e8953cc9d21b"),
+        ("withdraw (testnet)", ActionRequest::Withdraw { destination: DESTINATION.to_string(), amount: "1".to_string() }, TRANSFER_TIME, false, "b3172e33d2262dac2b4cb135ce3c167fda55dafa6c62213564ab728b9f9ba76b769a938e9f6d603dae7154c83bf5a4c3ebab81779dc2db25463a3ed663c82ae41c"),
+    ];
+
+    let count = vectors.len();
+    let mut failures = 0;
+    for (i, (name, request, nonce, is_mainnet, expected)) in vectors.into_iter().enumerate() {
+        println!("\n🧪 [TEST {}] {}:", i + 1, name);
+        let signed = build_action(request, Some(nonce), None, is_mainnet).and_then(|action| sign_action(action, signer.clone()));
+        match signed {
+            Ok(signed) if signed.signature == expected => {
+                println!("  ✅ Signature matches");
+                match verify_action_signature(signed, address.clone()) {
+                    Ok(true) => println!("  ✅ Signature verifies"),
+                    other => {
+                        println!("  ❌ Verification failed: {:?}", other);
+                        failures += 1;
+                    }
+                }
+            }
+            Ok(signed) => {
+                println!("  ❌ Got {}", signed.signature);
+                failures += 1;
+            }
+            Err(e) => {
+                println!("  ❌ Signing failed: {}", e);
+                failures += 1;
+            }
+        }
+    }
+
+    println!("\n🧪 [TEST {}] Tampered action must not verify:", count + 1);
+    let action = build_action(ActionRequest::Cancel { asset: 1, order_id: 82382 }, Some(NONCE), None, true).expect("Failed to build action");
+    let mut signed = sign_action(action, signer.clone()).expect("Failed to sign action");
+    signed.action_json = signed.action_json.replace("82382", "82383");
+    match verify_action_signature(signed, address.clone()) {
+        Ok(false) => println!("  ✅ Rejected"),
+        other => {
+            println!("  ❌ Unexpected result: {:?}", other);
+            failures += 1;
+        }
+    }
+
+    println!("\n🧪 [TEST {}] Transfers cannot be signed for a vault:", count + 2);
+    let request = ActionRequest::UsdSend { destination: DESTINATION.to_string(), amount: "1".to_string() };
+    match build_action(request, Some(TRANSFER_TIME), Some(DESTINATION.to_string()), false) {
+        Err(e) => println!("  ✅ Rejected: {}", e),
+        Ok(_) => {
+            println!("  ❌ Vault transfer was built");
+            failures += 1;
+        }
+    }
+
+    println!("\n🧪 [TEST {}] Builder fee on orders and builder approval:", count + 3);
+    let builder = BuilderFee { address: DESTINATION.to_string(), fee_tenths_bps: 10 };
+    let order = ActionRequest::Order {
+        asset: 1,
+        is_buy: true,
+        limit_price: "2000".to_string(),
+        size: "3.5".to_string(),
+        reduce_only: false,
+        time_in_force: "Ioc".to_string(),
+        cloid: None,
+        builder: Some(builder),
+    };
+    let approval = ActionRequest::ApproveBuilderFee { builder: DESTINATION.to_string(), max_fee_tenths_bps: 10 };
+    let expected_fragments = [
+        r#""builder":{"b":"0x0d1d9635d0640821d15e323ac8adadfa9c111414","f":10}"#,
+        r#""maxFeeRate":"0.01%","builder":"0x0d1d9635d0640821d15e323ac8adadfa9c111414","nonce":1583838"#,
+    ];
+    for (request, fragment) in [order, approval].into_iter().zip(expected_fragments) {
+        let signed = build_action(request, Some(NONCE), None, true).and_then(|action| sign_action(action, signer.clone()));
+        match signed {
+            Ok(signed) if signed.action_json.contains(fragment) => match verify_action_signature(signed, address.clone()) {
+                Ok(true) => println!("  ✅ {}", fragment),
+                other => {
+                    println!("  ❌ Verification failed: {:?}", other);
+                    failures += 1;
+                }
+            },
+            Ok(signed) => {
+                println!("  ❌ Unexpected action: {}", signed.action_json);
+                failures += 1;
+            }
+            Err(e) => {
+                println!("  ❌ Signing failed: {}", e);
+                failures += 1;
+            }
+        }
+    }
+
+    // Perps cap builder fees at 0.1%, spot (assets from 10000) at 1%
+    for (asset, fee, accepted) in [(1, 100, true), (1, 101, false), (10000, 1000, true), (10000, 1001, false)] {
+        let order = ActionRequest::Order {
+            asset,
+            is_buy: true,
+            limit_price: "2000".to_string(),
+            size: "3.5".to_string(),
+            reduce_only: false,
+            time_in_force: "Ioc".to_string(),
+            cloid: None,
+            builder: Some(BuilderFee { address: DESTINATION.to_string(), fee_tenths_bps: fee }),
+        };
+        match build_action(order, Some(NONCE), None, true) {
+            Ok(_) if accepted => println!("  ✅ Asset {} with builder fee {} built", asset, fee),
+            Err(e) if !accepted => println!("  ✅ Asset {} with builder fee {} rejected: {}", asset, fee, e),
+            other => {
+                println!("  ❌ Asset {} with builder fee {}: {:?}", asset, fee, other.map(|action| action.action_json));
+                failures += 1;
+            }
+        }
+    }
+
+    // Client-side builder configuration is validated the same way
+    let client = HyperliquidClient::new();
+    for (builder, fee, accepted) in [(DESTINATION, 10, true), (DESTINATION, 1001, false), ("not-an-address", 10, false)] {
+        let result = client.set_builder(builder.to_string(), fee);
+        if result.success == accepted {
+            println!("  ✅ set_builder({}, {}): {}", builder, fee, result.message);
+        } else {
+            println!("  ❌ set_builder({}, {}): {}", builder, fee, result.message);
+            failures += 1;
+        }
+    }
+    let fee = client.get_builder().map(|builder| builder.fee_tenths_bps);
+    if fee == Some(10) {
+        println!("  ✅ Rejected settings left the builder unchanged");
+    } else {
+        println!("  ❌ Builder fee is now {:?}", fee);
+        failures += 1;
+    }
+
+    // Wire format from the exchange API docs; L1 actions are hashed as msgpack in this field order
+    println!("\n🧪 [TEST {}] Sub-account actions:", count + 4);
+    let sub_account = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414";
+    let cases = [
+        (
+            ActionRequest::CreateSubAccount { name: "trading".to_string() },
+            None,
+            r#"{"type":"createSubAccount","name":"trading"}"#,
+        ),
+        (
+            ActionRequest::SubAccountTransfer { sub_account: sub_account.to_string(), is_deposit: true, usd: 1_000_000 },
+            None,
+            r#"{"type":"subAccountTransfer","subAccountUser":"0x0d1d9635d0640821d15e323ac8adadfa9c111414","isDeposit":true,"usd":1000000}"#,
+        ),
+        (
+            ActionRequest::SubAccountSpotTransfer {
+                sub_account: sub_account.to_string(),
+                is_deposit: false,
+                token: "PURR:0xc1fb593aeffbeb02f85e0308e9956a90".to_string(),
+                amount: "12.5".to_string(),
+            },
+            None,
+            r#"{"type":"subAccountSpotTransfer","subAccountUser":"0x0d1d9635d0640821d15e323ac8adadfa9c111414","isDeposit":false,"token":"PURR:0xc1fb593aeffbeb02f85e0308e9956a90","amount":"12.5"}"#,
+        ),
+        // Sub-account orders are signed by the master and routed through the vault address field
+        (
+            ActionRequest::Cancel { asset: 1, order_id: 82382 },
+            Some(sub_account.to_string()),
+            r#"{"type":"cancel","cancels":[{"a":1,"o":82382}]}"#,
+        ),
+    ];
+    failures += check_actions(cases, &signer, &address);
+
+    let invalid = ActionRequest::SubAccountSpotTransfer {
+        sub_account: sub_account.to_string(),
+        is_deposit: true,
+        token: "PURR:0xc1fb593aeffbeb02f85e0308e9956a90".to_string(),
+        amount: "1e3".to_string(),
+    };
+    match build_action(invalid, Some(NONCE), None, true) {
+        Err(e) => println!("  ✅ Rejected: {}", e),
+        Ok(_) => {
+            println!("  ❌ Exponent amount was built");
+            failures += 1;
+        }
+    }
+
+    println!("\n🧪 [TEST {}] Vault transfers:", count + 5);
+    let vault = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303";
+    let cases = [
+        (
+            ActionRequest::VaultTransfer { vault_address: vault.to_string(), is_deposit: true, usd: 25_500_000 },
+            None,
+            r#"{"type":"vaultTransfer","vaultAddress":"0xdfc24b077bc1425ad1dea75bcb6f8158e10df303","isDeposit":true,"usd":25500000}"#,
+        ),
+        (
+            ActionRequest::VaultTransfer { vault_address: vault.to_uppercase().replace("0X", "0x"), is_deposit: false, usd: 1 },
+            None,
+            r#"{"type":"vaultTransfer","vaultAddress":"0xdfc24b077bc1425ad1dea75bcb6f8158e10df303","isDeposit":false,"usd":1}"#,
+        ),
+    ];
+    failures += check_actions(cases, &signer, &address);
+
+    if failures > 0 {
+        println!("\n❌ [ERROR] {} signing checks failed", failures);
+        std::process::exit(1);
+    }
+    println!("\n✅ [DEBUG] All tests completed!");
+}
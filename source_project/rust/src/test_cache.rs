@@ -0,0 +1,68 @@
+mod test_harness;
+
+use hyperliquid_sdk_swift::test_hooks::Store;
+use hyperliquid_sdk_swift::HyperliquidClient;
+use test_harness::Checker;
+
+fn check_ranges(checker: &mut Checker, db_path: &str) {
+    let store = Store::open(db_path).expect("Failed to open cache");
+    store.clear().expect("Failed to clear cache");
+    let missing = |key: &str, start: u64, end: u64| store.missing_ranges("candles", key, start, end).expect("Failed to read ranges");
+
+    checker.check_eq("Empty cache misses everything", missing("BTC:1h", 0, 1000), vec![(0, 1000)]);
+
+    store.add_range("candles", "BTC:1h", 100, 199).expect("Failed to add range");
+    store.add_range("candles", "BTC:1h", 300, 399).expect("Failed to add range");
+    checker.check_eq("Gaps around two ranges", missing("BTC:1h", 0, 500), vec![(0, 99), (200, 299), (400, 500)]);
+    checker.check_eq("Fully covered", missing("BTC:1h", 120, 180), vec![]);
+    checker.check_eq("Other keys are separate", missing("ETH:1h", 100, 199), vec![(100, 199)]);
+
+    // Overlapping both existing ranges merges all three into one
+    store.add_range("candles", "BTC:1h", 150, 350).expect("Failed to add range");
+    checker.check_eq("Overlapping ranges merge", missing("BTC:1h", 0, 500), vec![(0, 99), (400, 500)]);
+
+    // Adjacent ranges (end + 1 == start) merge as well
+    store.add_range("candles", "BTC:1h", 400, 449).expect("Failed to add range");
+    store.add_range("candles", "BTC:1h", 50, 99).expect("Failed to add range");
+    checker.check_eq("Adjacent ranges merge", missing("BTC:1h", 0, 500), vec![(0, 49), (450, 500)]);
+    store.add_range("candles", "BTC:1h", 0, 49).expect("Failed to add range");
+    store.add_range("candles", "BTC:1h", 450, 500).expect("Failed to add range");
+    checker.check_eq("Filled gaps leave nothing missing", missing("BTC:1h", 0, 500), vec![]);
+
+    // Only the settled part is recorded, the rest is fetched again next time
+    store.add_settled_range("candles", "ETH:1h", 1000, 2000, 1499).expect("Failed to add range");
+    checker.check_eq("Unsettled tail stays missing", missing("ETH:1h", 1000, 2000), vec![(1500, 2000)]);
+    store.add_settled_range("candles", "ETH:1h", 1500, 2000, 1499).expect("Failed to add range");
+    checker.check_eq("Nothing settled records nothing", missing("ETH:1h", 1000, 2000), vec![(1500, 2000)]);
+
+    // Times past what SQLite can hold saturate instead of wrapping negative
+    store.add_range("candles", "BTC:1m", 1000, u64::MAX).expect("Failed to add range");
+    checker.check_eq("Open-ended range covered", missing("BTC:1m", 500, u64::MAX), vec![(500, 999)]);
+
+    store.clear().expect("Failed to clear cache");
+    checker.check_eq("Clear forgets fetched ranges", missing("BTC:1h", 0, 500), vec![(0, 500)]);
+}
+
+fn main() {
+    println!("🗄️ [DEBUG] Testing the SQLite candle and fill cache offline");
+    println!("==========================================================");
+
+    let db_path = std::env::temp_dir().join("hyperliquid_test_cache.sqlite");
+    let mut checker = Checker::new();
+
+    println!("\n🧪 [TEST 1] Fetched range bookkeeping:");
+    check_ranges(&mut checker, &db_path.to_string_lossy());
+
+    println!("\n🧪 [TEST 2] Opening the cache:");
+    let client = HyperliquidClient::new();
+    match client.enable_cache("/nonexistent/dir/cache.sqlite".to_string()) {
+        Err(e) => checker.check_that(&format!("unwritable path rejected: {}", e), true),
+        Ok(()) => checker.check_that("unwritable path rejected", false),
+    }
+    checker.check_that("cache opened", client.enable_cache(db_path.to_string_lossy().to_string()).is_ok());
+    checker.check_that("cache cleared", client.clear_cache().is_ok());
+    client.disable_cache();
+
+    checker.finish();
+    println!("\n✅ [DEBUG] All tests completed!");
+}
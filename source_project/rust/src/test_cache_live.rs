@@ -0,0 +1,40 @@
+use hyperliquid_sdk_swift::{CandleInterval, HyperliquidClient};
+use std::time::Instant;
+
+fn main() {
+    println!("🗄️ [DEBUG] Timing the SQLite cache against the exchange");
+    println!("=======================================================");
+
+    let address = "0xa07d3500373300E7f4e13c440c3A0Ae9Ad5BB7C7";
+    let client = HyperliquidClient::new_watch_only(address.to_string()).expect("Invalid address");
+    let db_path = std::env::temp_dir().join("hyperliquid_test_cache_live.sqlite");
+    client.enable_cache(db_path.to_string_lossy().to_string()).expect("Failed to open cache");
+    client.clear_cache().expect("Failed to clear cache");
+    println!("✅ [DEBUG] Cache at {}", db_path.display());
+
+    let end_time = chrono::Utc::now().timestamp_millis() as u64;
+    let start_time = end_time - 30 * 24 * 60 * 60 * 1000;
+
+    println!("\n🧪 [TEST 1] 30 days of BTC 1h candles, cold then warm:");
+    let started = Instant::now();
+    let cold = client.get_candles_range("BTC".to_string(), CandleInterval::OneHour, start_time, end_time);
+    println!("  • Cold: {} candles in {:?}", cold.candles.len(), started.elapsed());
+    let started = Instant::now();
+    let warm = client.get_candles_range("BTC".to_string(), CandleInterval::OneHour, start_time, end_time);
+    println!("  • Warm: {} candles in {:?}", warm.candles.len(), started.elapsed());
+    let same = cold.candles.len() == warm.candles.len()
+        && cold.candles.iter().zip(&warm.candles).all(|(a, b)| a.time_open == b.time_open && a.open == b.open);
+    println!("  {} Cached candles match the fetched ones", if same { "✅" } else { "❌" });
+
+    println!("\n🧪 [TEST 2] 30 days of fills, cold then warm:");
+    let started = Instant::now();
+    let cold = client.get_user_fills_by_time(address.to_string(), start_time, None);
+    println!("  • Cold: {} fills in {:?}", cold.len(), started.elapsed());
+    let started = Instant::now();
+    let warm = client.get_user_fills_by_time(address.to_string(), start_time, None);
+    println!("  • Warm: {} fills in {:?}", warm.len(), started.elapsed());
+    println!("  {} Same number of fills", if cold.len() == warm.len() { "✅" } else { "❌" });
+
+    client.disable_cache();
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -1,13 +1,99 @@
-use hyperliquid_rust_sdk::{BaseUrl, InfoClient};
-use tokio;
+mod test_harness;
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+use hyperliquid_sdk_swift::{
+    candle_interval_millis, candle_open_time, next_candle_open, parse_candle_interval, resample_candles,
+    resample_candles_minutes, CandleData, CandleInterval, HyperliquidClient,
+};
+use test_harness::Checker;
+
+fn synthetic_candle(time_open: u64, minutes: u64, ohlcv: [&str; 5], num_trades: u64) -> CandleData {
+    let [open, high, low, close, volume] = ohlcv.map(|value| value.to_string());
+    CandleData {
+        time_open,
+        time_close: time_open + minutes * 60 * 1000 - 1,
+        coin: "BTC".to_string(),
+        interval: format!("{}m", minutes),
+        open,
+        close,
+        high,
+        low,
+        volume,
+        num_trades,
+    }
+}
+
+fn main() {
     println!("🧪 Testing Hyperliquid Candles API...");
     
-    // Create InfoClient
-    let info = InfoClient::new(None, Some(BaseUrl::Mainnet)).await?;
-    println!("✅ InfoClient created successfully");
+    let mut checker = Checker::new();
+    
+    println!("\n📊 Testing typed intervals...");
+    // 2024-03-14 15:30:00 UTC, a Thursday
+    let time = 1710430200000;
+    let checks = [
+        ("1H is not an interval", parse_candle_interval("1H".to_string()).is_none()),
+        ("1M is a month", parse_candle_interval("1M".to_string()) == Some(CandleInterval::OneMonth)),
+        ("4h lasts 4 hours", candle_interval_millis(CandleInterval::FourHours) == 4 * 60 * 60 * 1000),
+        ("4h candle opens at 12:00", candle_open_time(CandleInterval::FourHours, time) == 1710417600000),
+        ("1w candle opens Monday 03-11", candle_open_time(CandleInterval::OneWeek, time) == 1710115200000),
+        ("1M candle opens 03-01", candle_open_time(CandleInterval::OneMonth, time) == 1709251200000),
+        ("next 1M candle opens 04-01", next_candle_open(CandleInterval::OneMonth, 1709251200000) == 1711929600000),
+    ];
+    for (name, passed) in checks {
+        checker.check_that(name, passed);
+    }
+    
+    println!("\n📊 Testing resampling...");
+    // 2024-03-14 00:00 UTC; 1h candles from 01:00 to 05:00, with 03:00 missing
+    let day = 1710374400000;
+    let hour = 60 * 60 * 1000;
+    let hourly = vec![
+        synthetic_candle(day + hour, 60, ["100", "105", "99", "104", "1.5"], 10),
+        synthetic_candle(day + 2 * hour, 60, ["104", "110", "103", "108", "2.25"], 20),
+        synthetic_candle(day + 4 * hour, 60, ["108", "109", "95", "97", "0.1"], 5),
+        synthetic_candle(day + 5 * hour, 60, ["97", "101", "96", "100", "0.2"], 7),
+    ];
+    let four_hourly = resample_candles(hourly, CandleInterval::FourHours);
+    let first = &four_hourly[0];
+    let second = &four_hourly[1];
+    let checks = [
+        ("two 4h buckets", four_hourly.len() == 2),
+        ("buckets open at 00:00 and 04:00", first.time_open == day && second.time_open == day + 4 * hour),
+        ("first bucket closes at 03:59:59.999", first.time_close == day + 4 * hour - 1),
+        ("OHLC of 00:00 bucket", [&first.open, &first.high, &first.low, &first.close] == ["100", "110", "99", "108"]),
+        ("OHLC of 04:00 bucket", [&second.open, &second.high, &second.low, &second.close] == ["108", "109", "95", "100"]),
+        ("volume summed without float noise", first.volume == "3.75" && second.volume == "0.3"),
+        ("trades summed", first.num_trades == 30 && second.num_trades == 12),
+        ("interval relabelled", first.interval == "4h"),
+    ];
+    for (name, passed) in checks {
+        checker.check_that(name, passed);
+    }
+    
+    let minutes: Vec<CandleData> = (0..5)
+        .map(|i| synthetic_candle(day + i * 60 * 1000, 1, ["1", "2", "0.5", "1.5", "1"], 1))
+        .collect();
+    let weekly = resample_candles(minutes.clone(), CandleInterval::OneWeek);
+    let week_of_minutes = resample_candles_minutes(minutes.clone(), 7 * 24 * 60);
+    let two_minute = resample_candles_minutes(minutes, 2);
+    let too_long = resample_candles(vec![synthetic_candle(day, 240, ["1", "1", "1", "1", "1"], 1)], CandleInterval::OneHour);
+    let checks = [
+        ("five 1m candles make three 2m buckets", two_minute.len() == 3 && two_minute[0].interval == "2m"),
+        ("last 2m bucket is partial", two_minute[2].num_trades == 1),
+        ("longer candles are not split", too_long.is_empty()),
+        ("1w buckets open on Monday", weekly.first().map(|candle| candle.time_open) == Some(1710115200000)),
+        ("10080m buckets align to the epoch", week_of_minutes.first().map(|candle| candle.time_open) == Some(day)),
+    ];
+    for (name, passed) in checks {
+        checker.check_that(name, passed);
+    }
+    
+    // Offline checks gate the run before anything touches the network
+    checker.finish();
+    
+    // Requests go through the client's shared rate limiter, no manual pacing needed
+    let client = HyperliquidClient::new();
+    println!("✅ HyperliquidClient created successfully");
     
     // Test different coins and parameters
     let test_cases = vec![
@@ -28,27 +114,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("   Start time: {} ({})", start_time, chrono::DateTime::from_timestamp((start_time / 1000) as i64, 0).unwrap());
         println!("   End time: {} ({})", end_time, chrono::DateTime::from_timestamp((end_time / 1000) as i64, 0).unwrap());
         
-        match info.candles_snapshot(coin.to_string(), interval.to_string(), start_time, end_time).await {
-            Ok(candles) => {
-                println!("   ✅ SUCCESS: Retrieved {} candles", candles.len());
-                if !candles.is_empty() {
-                    let first = &candles[0];
-                    let last = &candles[candles.len() - 1];
-                    println!("   📈 First candle: {} open={}, close={}, high={}, low={}", 
-                             first.coin, first.open, first.close, first.high, first.low);
-                    println!("   📈 Last candle:  {} open={}, close={}, high={}, low={}", 
-                             last.coin, last.close, last.close, last.high, last.low);
-                }
-            }
-            Err(e) => {
-                println!("   ❌ FAILED: {}", e);
-            }
+        let candles = client.get_candles_snapshot(coin.to_string(), interval.to_string(), start_time, end_time);
+        println!("   ✅ SUCCESS: Retrieved {} candles", candles.len());
+        if let (Some(first), Some(last)) = (candles.first(), candles.last()) {
+            println!("   📈 First candle: {} open={}, close={}, high={}, low={}", 
+                     first.coin, first.open, first.close, first.high, first.low);
+            println!("   📈 Last candle:  {} open={}, close={}, high={}, low={}", 
+                     last.coin, last.open, last.close, last.high, last.low);
         }
-        
-        // Small delay between requests to be respectful
-        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
     }
     
+    // A year of 1h candles is ~8760, more than one request returns
+    println!("\n📊 Testing a year of BTC 1h candles over multiple requests...");
+    let end_time = chrono::Utc::now().timestamp() as u64 * 1000;
+    let start_time = end_time - 365 * 24 * 60 * 60 * 1000;
+    let range = client.get_candles_range("BTC".to_string(), CandleInterval::OneHour, start_time, end_time);
+    println!("   ✅ SUCCESS: Retrieved {} candles", range.candles.len());
+    let unordered = range.candles.windows(2).filter(|pair| pair[0].time_open >= pair[1].time_open).count();
+    checker.check_eq("Out of order or duplicate candles", unordered, 0);
+    for gap in &range.gaps {
+        println!("   ⚠️ Gap: {} candles missing from {} to {}", gap.missing_candles, gap.first_missing_open, gap.last_missing_open);
+    }
+    
+    println!("\n📊 Testing numeric candles...");
+    let end_time = chrono::Utc::now().timestamp() as u64 * 1000;
+    let candles = client.get_candles("ETH".to_string(), CandleInterval::FifteenMinutes, end_time - 6 * 60 * 60 * 1000, end_time);
+    println!("   ✅ SUCCESS: Retrieved {} numeric ETH 15m candles", candles.len());
+    if let Some(last) = candles.last() {
+        println!("   📈 Last candle: open={:.2} close={:.2} range={:.2}", last.open, last.close, last.high - last.low);
+    }
+    
+    checker.finish();
     println!("\n🏁 Testing complete!");
-    Ok(())
-}
\ No newline at end of file
+}
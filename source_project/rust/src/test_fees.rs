@@ -0,0 +1,29 @@
+use hyperliquid_sdk_swift::HyperliquidClient;
+
+fn main() {
+    println!("💸 [DEBUG] Testing fee tier and rate limit queries");
+    println!("=================================================");
+
+    let address = "0xa07d3500373300E7f4e13c440c3A0Ae9Ad5BB7C7";
+    let client = HyperliquidClient::new_watch_only(address.to_string()).expect("Invalid address");
+
+    println!("\n🧪 [TEST 1] User fees:");
+    let fees = client.get_user_fees(address.to_string());
+    println!("  • Perp maker/taker: {} / {}", fees.maker_rate, fees.taker_rate);
+    println!("  • Spot maker/taker: {} / {}", fees.spot_maker_rate, fees.spot_taker_rate);
+    println!("  • VIP tier {} (14d volume {})", fees.volume_tier, fees.fourteen_day_volume);
+    println!("  • Staking discount {}, referral discount {}", fees.staking_discount, fees.referral_discount);
+
+    println!("\n🧪 [TEST 2] Rate limit:");
+    let rate_limit = client.get_user_rate_limit(address.to_string());
+    println!("  • {} / {} requests used ({} left)", rate_limit.requests_used, rate_limit.requests_cap, rate_limit.requests_remaining);
+    println!("  • Cumulative volume: {}", rate_limit.cumulative_volume);
+
+    println!("\n🧪 [TEST 3] Swap quotes after fees:");
+    let buy = client.quote_usdc_to_btc("100".to_string());
+    println!("  • 100 USDC -> {} UBTC gross, {} {} fee, {} net", buy.size, buy.fee, buy.fee_token, buy.expected_proceeds);
+    let sell = client.quote_btc_to_usdc("0.001".to_string());
+    println!("  • 0.001 UBTC -> {} USDC net ({} {} fee)", sell.expected_proceeds, sell.fee, sell.fee_token);
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
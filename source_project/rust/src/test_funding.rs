@@ -0,0 +1,39 @@
+use hyperliquid_sdk_swift::{annualize_funding_rate, HyperliquidClient};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("💰 [DEBUG] Testing funding history and predicted funding");
+    println!("========================================================");
+
+    let client = HyperliquidClient::new();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+
+    println!("\n🧪 [TEST 1] Annualized rates:");
+    println!("  • 0.00125%/h -> {:.4}% per year", annualize_funding_rate(0.0000125, 1) * 100.0);
+    println!("  • 0.01%/8h -> {:.4}% per year", annualize_funding_rate(0.0001, 8) * 100.0);
+
+    // 30 days of hourly funding is 720 entries, more than one page
+    println!("\n🧪 [TEST 2] BTC funding over the last 30 days:");
+    let history = client.get_funding_history("BTC".to_string(), now - 30 * 24 * 60 * 60 * 1000, None);
+    println!("  ✅ {} entries", history.len());
+    let duplicates = history.windows(2).filter(|pair| pair[0].time >= pair[1].time).count();
+    println!("  • Out of order or duplicate entries: {}", duplicates);
+    if let (Some(first), Some(last)) = (history.first(), history.last()) {
+        println!("  • First {} rate={} / last {} rate={}", first.time, first.funding_rate, last.time, last.funding_rate);
+    }
+
+    println!("\n🧪 [TEST 3] Predicted fundings:");
+    let predicted = client.get_predicted_fundings();
+    println!("  ✅ {} coins", predicted.len());
+    for prediction in predicted.iter().filter(|p| p.coin == "BTC" || p.coin == "ETH") {
+        for venue in &prediction.venues {
+            println!(
+                "  • {} {:<9} rate={} every {}h ({:.2}% APR), next at {}",
+                prediction.coin, venue.venue, venue.funding_rate, venue.funding_interval_hours,
+                venue.annualized_rate * 100.0, venue.next_funding_time
+            );
+        }
+    }
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,56 @@
+// Shared by the offline test bins through `mod test_harness;`
+#![allow(dead_code)]
+
+use std::fmt::Debug;
+
+pub struct Checker {
+    pub failures: u32,
+    tolerance: f64,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self::with_tolerance(1e-9)
+    }
+
+    pub fn with_tolerance(tolerance: f64) -> Self {
+        Self { failures: 0, tolerance }
+    }
+
+    pub fn check(&mut self, name: &str, actual: f64, expected: f64) {
+        self.check_within(name, actual, expected, self.tolerance);
+    }
+
+    pub fn check_within(&mut self, name: &str, actual: f64, expected: f64, tolerance: f64) {
+        if (actual - expected).abs() <= tolerance {
+            println!("  ✅ {} = {}", name, actual);
+        } else {
+            println!("  ❌ {} = {}, expected {}", name, actual, expected);
+            self.failures += 1;
+        }
+    }
+
+    pub fn check_eq<T: PartialEq + Debug>(&mut self, name: &str, actual: T, expected: T) {
+        if actual == expected {
+            println!("  ✅ {}: {:?}", name, actual);
+        } else {
+            println!("  ❌ {}: got {:?}, expected {:?}", name, actual, expected);
+            self.failures += 1;
+        }
+    }
+
+    pub fn check_that(&mut self, name: &str, passed: bool) {
+        println!("  {} {}", if passed { "✅" } else { "❌" }, name);
+        if !passed {
+            self.failures += 1;
+        }
+    }
+
+    /// Exits with status 1 if any check failed.
+    pub fn finish(&self) {
+        if self.failures > 0 {
+            println!("\n❌ [DEBUG] {} checks failed", self.failures);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use hyperliquid_sdk_swift::{default_http_config, HyperliquidClient};
+
+fn main() {
+    println!("🌐 [DEBUG] Testing shared HTTP client configuration");
+    println!("==================================================");
+
+    let mut config = default_http_config();
+    config.connect_timeout_ms = 5_000;
+    config.request_timeout_ms = 15_000;
+    config.user_agent = Some("HyperliquidSwiftTest/1.0".to_string());
+    config.headers.insert("X-Client-Name".to_string(), "test_http_config".to_string());
+    if let Ok(proxy_url) = std::env::var("HTTPS_PROXY") {
+        println!("  • Using proxy {}", proxy_url);
+        config.proxy_url = Some(proxy_url);
+    }
+
+    // Test 1: bad settings from the app come back as errors
+    println!("\n🧪 [TEST 1] Rejecting invalid settings:");
+    let mut bad_proxy = config.clone();
+    bad_proxy.proxy_url = Some("not a url".to_string());
+    let mut bad_header = config.clone();
+    bad_header.headers.insert("Bad Header".to_string(), "x".to_string());
+    for (name, bad_config) in [("proxy URL", bad_proxy), ("header name", bad_header)] {
+        match HyperliquidClient::new_with_http_config(bad_config) {
+            Ok(_) => println!("  ❌ Invalid {} accepted", name),
+            Err(e) => println!("  ✅ {}", e),
+        }
+    }
+
+    // Test 2: SDK requests and our own info requests share the same client
+    println!("\n🧪 [TEST 2] Querying through the configured client:");
+    let client = match HyperliquidClient::new_with_http_config(config) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("❌ [ERROR] {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("  ✅ {} perp assets via the SDK", client.get_exchange_meta().total_assets);
+    let rate_limit = client.get_user_rate_limit("0xa07d3500373300E7f4e13c440c3A0Ae9Ad5BB7C7".to_string());
+    println!("  ✅ Rate limit {} / {} via a raw info request", rate_limit.requests_used, rate_limit.requests_cap);
+
+    // Test 3: repeated calls reuse the pooled connection instead of a new TLS handshake
+    println!("\n🧪 [TEST 3] Timing repeated requests:");
+    for i in 1..=3 {
+        let started = std::time::Instant::now();
+        let mids = client.get_all_mids();
+        println!("  • Request {}: {} mids in {:?}", i, mids.len(), started.elapsed());
+    }
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
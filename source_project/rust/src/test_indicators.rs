@@ -0,0 +1,155 @@
+mod test_harness;
+
+use hyperliquid_sdk_swift::{atr, bollinger_bands, ema, macd, rsi, sma, stochastic, vwap, CandleData};
+use test_harness::Checker;
+
+const HOUR: u64 = 60 * 60 * 1000;
+
+// Closes from StockCharts' EMA and RSI worked examples
+const EMA_CLOSES: [f64; 30] = [
+    22.27, 22.19, 22.08, 22.17, 22.18, 22.13, 22.23, 22.43, 22.24, 22.29, 22.15, 22.39, 22.38, 22.61, 23.36, 24.05,
+    23.75, 23.83, 23.95, 23.63, 23.82, 23.87, 23.65, 23.19, 23.10, 23.33, 22.68, 23.10, 22.40, 22.17,
+];
+const RSI_CLOSES: [f64; 33] = [
+    44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03, 45.61, 46.28, 46.28, 46.00,
+    46.03, 46.41, 46.22, 45.64, 46.21, 46.25, 45.71, 46.45, 45.78, 45.35, 44.03, 44.18, 44.22, 44.57, 43.42, 42.66,
+    43.13,
+];
+
+// Hourly candles from `closes`, with highs, lows and volumes varied deterministically
+fn candles(closes: &[f64]) -> Vec<CandleData> {
+    closes
+        .iter()
+        .enumerate()
+        .map(|(i, close)| CandleData {
+            time_open: i as u64 * HOUR,
+            time_close: (i as u64 + 1) * HOUR - 1,
+            coin: "TEST".to_string(),
+            interval: "1h".to_string(),
+            open: close.to_string(),
+            close: close.to_string(),
+            high: format!("{:.2}", close + 0.3 + (i % 3) as f64 * 0.1),
+            low: format!("{:.2}", close - 0.25 - (i % 4) as f64 * 0.05),
+            volume: (100 + i * 37 % 50).to_string(),
+            num_trades: 1,
+        })
+        .collect()
+}
+
+// Hourly candles from (high, low, close, volume)
+fn bars(bars: &[(f64, f64, f64, f64)]) -> Vec<CandleData> {
+    bars.iter()
+        .enumerate()
+        .map(|(i, (high, low, close, volume))| CandleData {
+            time_open: i as u64 * HOUR,
+            time_close: (i as u64 + 1) * HOUR - 1,
+            coin: "TEST".to_string(),
+            interval: "1h".to_string(),
+            open: close.to_string(),
+            close: close.to_string(),
+            high: high.to_string(),
+            low: low.to_string(),
+            volume: volume.to_string(),
+            num_trades: 1,
+        })
+        .collect()
+}
+
+fn check_time(checker: &mut Checker, name: &str, actual: Option<u64>, expected_index: u64) {
+    checker.check(name, actual.map_or(-1.0, |time| (time / HOUR) as f64), expected_index as f64);
+}
+
+fn main() {
+    println!("📈 [DEBUG] Testing technical indicators against reference values");
+    println!("===============================================================");
+
+    let mut checker = Checker::new();
+    let ema_candles = candles(&EMA_CLOSES);
+    let rsi_candles = candles(&RSI_CLOSES);
+
+    println!("\n🧪 [TEST 1] SMA(10) and EMA(10), StockCharts example:");
+    let sma_points = sma(ema_candles.clone(), 10);
+    check_time(&mut checker, "first SMA candle", sma_points.first().map(|p| p.time), 9);
+    checker.check("first SMA", sma_points[0].value, 22.221);
+    checker.check("last SMA", sma_points[sma_points.len() - 1].value, 23.131);
+    let ema_points = ema(ema_candles.clone(), 10);
+    // Published to 2 decimals, from rounded intermediate values
+    let published = [22.22, 22.21, 22.24, 22.27, 22.33, 22.52, 22.80, 22.97, 23.13, 23.28, 23.34, 23.43, 23.51, 23.54, 23.47, 23.40, 23.39, 23.26, 23.23, 23.08, 22.92];
+    checker.check("EMA points", ema_points.len() as f64, published.len() as f64);
+    for (point, expected) in ema_points.iter().zip(published) {
+        checker.check_within(&format!("EMA at candle {}", point.time / HOUR), point.value, expected, 0.011);
+    }
+
+    println!("\n🧪 [TEST 2] RSI(14), StockCharts example:");
+    let rsi_points = rsi(rsi_candles.clone(), 14);
+    check_time(&mut checker, "first RSI candle", rsi_points.first().map(|p| p.time), 14);
+    // Published from rounded averages, so within 0.1
+    let published = [70.53, 66.32, 66.55, 69.41, 66.36, 57.97, 62.93, 63.26, 56.06, 62.38, 54.71, 50.42, 39.99, 41.46, 41.87, 45.46, 37.30, 33.08, 37.77];
+    for (point, expected) in rsi_points.iter().zip(published) {
+        checker.check_within(&format!("RSI at candle {}", point.time / HOUR), point.value, expected, 0.1);
+    }
+
+    // The rest are small cases worked out by hand, values in the comments
+    println!("\n🧪 [TEST 3] MACD(2, 3, 2) on closes 10, 12, 11, 14, 13, 16:");
+    // EMA(2) from candle 1: 11, 11, 13, 13, 15; EMA(3) from candle 2: 11, 12.5, 12.75, 14.375;
+    // MACD from candle 2: 0, 0.5, 0.25, 0.625; its EMA(2) from candle 3: 0.25, 0.25, 0.5
+    let macd_points = macd(candles(&[10.0, 12.0, 11.0, 14.0, 13.0, 16.0]), 2, 3, 2);
+    checker.check("points", macd_points.len() as f64, 3.0);
+    check_time(&mut checker, "first MACD candle", macd_points.first().map(|p| p.time), 3);
+    let expected = [(0.5, 0.25, 0.25), (0.25, 0.25, 0.0), (0.625, 0.5, 0.125)];
+    for (point, (macd, signal, histogram)) in macd_points.iter().zip(expected) {
+        let candle = point.time / HOUR;
+        checker.check(&format!("MACD at candle {}", candle), point.macd, macd);
+        checker.check(&format!("signal at candle {}", candle), point.signal, signal);
+        checker.check(&format!("histogram at candle {}", candle), point.histogram, histogram);
+    }
+
+    println!("\n🧪 [TEST 4] Bollinger Bands(8, 2) on closes 2, 4, 4, 4, 5, 5, 7, 9:");
+    // Mean 5, population standard deviation 2
+    let bands = bollinger_bands(candles(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]), 8, 2.0);
+    checker.check("points", bands.len() as f64, 1.0);
+    check_time(&mut checker, "band candle", bands.first().map(|p| p.time), 7);
+    checker.check("middle", bands[0].middle, 5.0);
+    checker.check("upper", bands[0].upper, 9.0);
+    checker.check("lower", bands[0].lower, 1.0);
+
+    println!("\n🧪 [TEST 5] ATR(3):");
+    // True ranges from candle 1: 2, 4 (gap up from 10), 3 (down to 10 from 13), 1;
+    // ATR (2 + 4 + 3) / 3 = 3, then (3 * 2 + 1) / 3
+    let atr_points = atr(bars(&[(10.0, 8.0, 9.0, 1.0), (11.0, 9.0, 10.0, 1.0), (14.0, 12.0, 13.0, 1.0), (13.0, 10.0, 11.0, 1.0), (12.0, 11.0, 12.0, 1.0)]), 3);
+    checker.check("points", atr_points.len() as f64, 2.0);
+    check_time(&mut checker, "first ATR candle", atr_points.first().map(|p| p.time), 3);
+    checker.check("first ATR", atr_points[0].value, 3.0);
+    checker.check("second ATR", atr_points[1].value, 7.0 / 3.0);
+
+    println!("\n🧪 [TEST 6] VWAP:");
+    // Typical prices 5, 10, 20, 30 with volumes 0, 1, 3, 0: nothing until candle 1,
+    // then 10, (10 + 60) / 4, and unchanged without volume
+    let vwap_points = vwap(bars(&[(5.0, 5.0, 5.0, 0.0), (11.0, 9.0, 10.0, 1.0), (22.0, 18.0, 20.0, 3.0), (31.0, 29.0, 30.0, 0.0)]));
+    checker.check("points", vwap_points.len() as f64, 3.0);
+    check_time(&mut checker, "first VWAP candle", vwap_points.first().map(|p| p.time), 1);
+    let expected = [10.0, 17.5, 17.5];
+    for (point, expected) in vwap_points.iter().zip(expected) {
+        checker.check(&format!("VWAP at candle {}", point.time / HOUR), point.value, expected);
+    }
+
+    println!("\n🧪 [TEST 7] Stochastic(3, 2):");
+    // %K from candle 2: (8 - 7) / (12 - 7), (12 - 7) / (17 - 7), (15 - 7) / (17 - 7), (20 - 10) / (20 - 10);
+    // %D from candle 3 averages the last two
+    let stochastic_points = stochastic(
+        bars(&[(10.0, 8.0, 9.0, 1.0), (12.0, 9.0, 11.0, 1.0), (11.0, 7.0, 8.0, 1.0), (17.0, 10.0, 12.0, 1.0), (15.0, 12.0, 15.0, 1.0), (20.0, 15.0, 20.0, 1.0)]),
+        3,
+        2,
+    );
+    checker.check("points", stochastic_points.len() as f64, 3.0);
+    check_time(&mut checker, "first stochastic candle", stochastic_points.first().map(|p| p.time), 3);
+    let expected = [(50.0, 35.0), (80.0, 65.0), (100.0, 90.0)];
+    for (point, (k, d)) in stochastic_points.iter().zip(expected) {
+        let candle = point.time / HOUR;
+        checker.check(&format!("%K at candle {}", candle), point.k, k);
+        checker.check(&format!("%D at candle {}", candle), point.d, d);
+    }
+
+    checker.finish();
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,86 @@
+use hyperliquid_sdk_swift::{create_keystore, derive_address_from_private_key, export_keystore, import_keystore};
+
+fn main() {
+    println!("🔐 [DEBUG] Testing encrypted keystore import/export");
+    println!("==================================================");
+
+    let password = match std::env::var("HYPERLIQUID_KEYSTORE_PASSWORD") {
+        Ok(password) if !password.is_empty() => password,
+        _ => {
+            println!("❌ [ERROR] HYPERLIQUID_KEYSTORE_PASSWORD must be set to a non-empty password");
+            std::process::exit(1);
+        }
+    };
+
+    // Test 1: brand new key, only ever seen encrypted
+    println!("\n🧪 [TEST 1] Creating a new keystore:");
+    let created = match create_keystore(password.clone()) {
+        Ok(info) => info,
+        Err(e) => {
+            println!("❌ [ERROR] create_keystore failed: {}", e);
+            return;
+        }
+    };
+    println!("  • Address: {}", created.address);
+    match import_keystore(created.json.clone(), password.clone()) {
+        Ok(signer) if signer.address() == created.address => println!("  ✅ Round trip recovers the same address"),
+        Ok(signer) => println!("  ❌ Round trip recovered {} instead", signer.address()),
+        Err(e) => println!("  ❌ Import failed: {}", e),
+    }
+
+    // Test 2: a wrong password must be rejected, not decrypt to garbage
+    println!("\n🧪 [TEST 2] Importing with the wrong password:");
+    match import_keystore(created.json.clone(), format!("{}-wrong", password)) {
+        Ok(_) => println!("  ❌ Wrong password was accepted"),
+        Err(e) => println!("  ✅ Rejected: {}", e),
+    }
+
+    // Test 3: KDF parameters a hostile file could use to hang the app
+    println!("\n🧪 [TEST 3] Importing keystores with oversized KDF parameters:");
+    let mut hostile: serde_json::Value = serde_json::from_str(&created.json).unwrap_or_default();
+    hostile["crypto"]["kdfparams"]["n"] = serde_json::json!(1u64 << 30);
+    match import_keystore(hostile.to_string(), password.clone()) {
+        Ok(_) => println!("  ❌ scrypt n = 2^30 was accepted"),
+        Err(e) => println!("  ✅ Rejected: {}", e),
+    }
+    hostile["crypto"]["kdfparams"]["n"] = serde_json::json!(8192);
+    hostile["crypto"]["kdfparams"]["p"] = serde_json::json!(u32::MAX);
+    match import_keystore(hostile.to_string(), password.clone()) {
+        Ok(_) => println!("  ❌ scrypt r·p = 2^35 was accepted"),
+        Err(e) => println!("  ✅ Rejected: {}", e),
+    }
+
+    // Test 4: migrate the old plaintext key file if there is one
+    println!("\n🧪 [TEST 4] Migrating private-key.key to private-key.json:");
+    let private_key = match std::fs::read_to_string("private-key.key") {
+        Ok(key) => key.trim().to_string(),
+        Err(_) => {
+            println!("  ℹ️ No private-key.key found, nothing to migrate");
+            println!("\n✅ [DEBUG] All tests completed!");
+            return;
+        }
+    };
+
+    let json = match export_keystore(private_key.clone(), password.clone()) {
+        Ok(json) => json,
+        Err(e) => {
+            println!("  ❌ export_keystore failed: {}", e);
+            return;
+        }
+    };
+    let expected = derive_address_from_private_key(private_key);
+    match import_keystore(json.clone(), password) {
+        Ok(signer) if signer.address() == expected => {
+            if let Err(e) = std::fs::write("private-key.json", json) {
+                println!("  ❌ Could not write private-key.json: {}", e);
+                return;
+            }
+            println!("  ✅ Wrote private-key.json for {}", expected);
+            println!("  💡 private-key.key can now be deleted");
+        }
+        Ok(signer) => println!("  ❌ Keystore recovered {} instead of {}", signer.address(), expected),
+        Err(e) => println!("  ❌ Import failed: {}", e),
+    }
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
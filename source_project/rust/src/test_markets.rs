@@ -0,0 +1,30 @@
+use hyperliquid_sdk_swift::{AssetContext, HyperliquidClient};
+
+fn print_top(contexts: &mut [AssetContext]) {
+    contexts.sort_by(|a, b| b.volume_24h.total_cmp(&a.volume_24h));
+    for ctx in contexts.iter().take(5) {
+        println!(
+            "  • {:<12} mark={} change={:+.2}% vol=${:.0} oi={:?} funding={:?}",
+            ctx.display_name, ctx.mark_price, ctx.change_24h_percent, ctx.volume_24h, ctx.open_interest, ctx.funding_rate
+        );
+    }
+}
+
+fn main() {
+    println!("📊 [DEBUG] Testing asset contexts for the markets screen");
+    println!("=======================================================");
+
+    let client = HyperliquidClient::new();
+
+    println!("\n🧪 [TEST 1] Top perps by 24h volume:");
+    let mut perps = client.get_perp_asset_contexts();
+    println!("  ✅ {} perp contexts", perps.len());
+    print_top(&mut perps);
+
+    println!("\n🧪 [TEST 2] Top spot pairs by 24h volume:");
+    let mut spot = client.get_spot_asset_contexts();
+    println!("  ✅ {} spot contexts", spot.len());
+    print_top(&mut spot);
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
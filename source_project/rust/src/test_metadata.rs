@@ -0,0 +1,56 @@
+use hyperliquid_sdk_swift::HyperliquidClient;
+use std::time::Instant;
+
+fn main() {
+    println!("🗂️ [DEBUG] Testing metadata cache");
+    println!("=================================");
+
+    let client = HyperliquidClient::new();
+
+    // Test 1: the first call fetches, later calls within the TTL are served from memory
+    println!("\n🧪 [TEST 1] Cached perp and spot metadata:");
+    for i in 1..=3 {
+        let started = Instant::now();
+        let meta = client.get_exchange_meta();
+        let pairs = client.get_spot_meta();
+        println!("  • Call {}: {} perps, {} spot pairs in {:?}", i, meta.total_assets, pairs.len(), started.elapsed());
+    }
+
+    // Test 2: explicit refresh always goes to the network
+    println!("\n🧪 [TEST 2] Explicit refresh:");
+    let started = Instant::now();
+    match client.refresh_metadata() {
+        Ok(()) => println!("  ✅ Refreshed in {:?}", started.elapsed()),
+        Err(e) => println!("  ❌ {}", e),
+    }
+
+    // Test 3: sz_decimals from the cache drive rounding
+    println!("\n🧪 [TEST 3] Asset precision:");
+    for asset in client.get_exchange_meta().assets.iter().take(5) {
+        println!("  • {}: {} size decimals", asset.name, asset.sz_decimals);
+    }
+    let quote = client.quote_usdc_to_btc("100".to_string());
+    println!("  • 100 USDC buys {} UBTC at {}", quote.size, quote.price);
+
+    // Test 4: typed spot pairs and tokens
+    println!("\n🧪 [TEST 4] Spot pairs and tokens:");
+    let pairs = client.get_spot_pairs();
+    for pair in pairs.iter().filter(|pair| pair.base == "UBTC" || pair.is_canonical).take(5) {
+        println!("  • {} = {} (canonical: {}, {} size decimals)", pair.id, pair.name, pair.is_canonical, pair.sz_decimals);
+    }
+    let tokens = client.get_spot_tokens();
+    if let Some(ubtc) = tokens.iter().find(|token| token.name == "UBTC") {
+        println!("  • UBTC token {} ({:?}), EVM contract {:?}", ubtc.token_id, ubtc.full_name, ubtc.evm_contract);
+        match client.get_token_details(ubtc.token_id.clone()) {
+            Some(details) => println!("  • Supply {} / {} circulating, deployed by {:?}", details.total_supply, details.circulating_supply, details.deployer),
+            None => println!("  ❌ No details for {}", ubtc.token_id),
+        }
+    }
+    for token_id in ["UBTC", "0x1234", "0x00000000000000000000000000000000"] {
+        let status = if client.get_token_details(token_id.to_string()).is_none() { "✅" } else { "❌" };
+        println!("  {} No details for {}", status, token_id);
+    }
+
+    client.set_metadata_ttl(60);
+    println!("\n✅ [DEBUG] All tests completed!");
+}
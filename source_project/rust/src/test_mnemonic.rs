@@ -0,0 +1,71 @@
+mod test_harness;
+
+use hyperliquid_sdk_swift::{derive_mnemonic_accounts, generate_mnemonic, validate_mnemonic, HyperliquidClient};
+use test_harness::Checker;
+
+fn main() {
+    println!("🌱 [DEBUG] Testing BIP-39 mnemonic wallets");
+    println!("==========================================");
+
+    let mut checker = Checker::new();
+
+    // Known vectors: Hardhat's default phrase and the all-"abandon" phrase
+    let test_cases = vec![
+        ("test test test test test test test test test test test junk", 0, "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"),
+        ("test test test test test test test test test test test junk", 1, "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"),
+        ("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about", 0, "0x9858effd232b4033e47d90003d41ec34ecaeda94"),
+    ];
+
+    println!("\n🧪 [TEST 1] Deriving m/44'/60'/0'/0/i from known phrases:");
+    for (phrase, index, expected) in test_cases {
+        match derive_mnemonic_accounts(phrase.to_string(), index + 1) {
+            Ok(accounts) => {
+                let account = &accounts[index as usize];
+                checker.check_eq(&account.derivation_path, account.address.as_str(), expected);
+            }
+            Err(e) => checker.check_that(&format!("derivation failed: {}", e), false),
+        }
+    }
+
+    println!("\n🧪 [TEST 2] Validating user-entered phrases:");
+    let phrases = vec![
+        ("  Test test TEST test test test test test test test test junk\n", true),
+        ("test test test test test test test test test test test test", false), // bad checksum
+        ("test test test", false),
+        ("notaword test test test test test test test test test test junk", false),
+    ];
+    for (phrase, expected) in phrases {
+        checker.check_eq(&format!("{:?} valid", phrase.trim()), validate_mnemonic(phrase.to_string()), expected);
+    }
+
+    println!("\n🧪 [TEST 3] Generating new phrases:");
+    for word_count in [12, 24] {
+        match generate_mnemonic(word_count) {
+            Ok(phrase) => {
+                checker.check_eq(&format!("{} words generated", word_count), phrase.split(' ').count(), word_count as usize);
+                checker.check_that(&format!("{} word phrase round trips", word_count), validate_mnemonic(phrase));
+            }
+            Err(e) => checker.check_that(&format!("{} words failed: {}", word_count, e), false),
+        }
+    }
+    match generate_mnemonic(13) {
+        Ok(_) => checker.check_that("13 words rejected", false),
+        Err(e) => checker.check_that(&format!("13 words rejected: {}", e), true),
+    }
+    checker.finish();
+
+    println!("\n📊 [TEST 4] First 3 accounts with balances:");
+    let client = HyperliquidClient::new();
+    match client.list_mnemonic_accounts("test test test test test test test test test test test junk".to_string(), 3) {
+        Ok(accounts) => {
+            for account in accounts {
+                println!("  • #{} {} account value ${}, {} spot balances", account.index, account.address, account.account_value, account.balances.len());
+            }
+        }
+        Err(e) => checker.check_that(&format!("listing failed: {}", e), false),
+    }
+
+    checker.finish();
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,157 @@
+mod test_harness;
+
+use hyperliquid_sdk_swift::{HyperliquidClient, OrderLevel, OrderbookData, PaperConfig, PaperFeed};
+use std::collections::HashMap;
+use test_harness::Checker;
+
+const TAKER: f64 = 0.001;
+const MAKER: f64 = 0.0005;
+
+fn book(bids: &[(&str, &str)], asks: &[(&str, &str)]) -> OrderbookData {
+    let levels = |levels: &[(&str, &str)]| {
+        levels
+            .iter()
+            .map(|(price, size)| OrderLevel {
+                price: price.to_string(),
+                size: size.to_string(),
+            })
+            .collect()
+    };
+    OrderbookData {
+        bids: levels(bids),
+        asks: levels(asks),
+    }
+}
+
+fn balance(client: &HyperliquidClient, token: &str) -> (f64, f64) {
+    client
+        .get_balances().unwrap_or_default()
+        .iter()
+        .find(|balance| balance.coin == token)
+        .map_or((0.0, 0.0), |balance| (balance.total.parse().unwrap_or(0.0), balance.hold.parse().unwrap_or(0.0)))
+}
+
+fn main() {
+    println!("🧪 [DEBUG] Testing paper trading on a recorded feed");
+    println!("===================================================");
+
+    let mut checker = Checker::with_tolerance(1e-6);
+    let client = HyperliquidClient::new_paper_trading(PaperConfig {
+        feed: PaperFeed::Recorded,
+        balances: HashMap::from([("USDC".to_string(), 10_000.0)]),
+        taker_fee_rate: TAKER,
+        maker_fee_rate: MAKER,
+        leverage: 2,
+        address: None,
+    })
+    .expect("Invalid address");
+
+    println!("\n🧪 [TEST 1] Client state and recorded market data:");
+    checker.check_that("is paper trading", client.is_paper_trading());
+    checker.check_that("is not watch-only", !client.is_watch_only());
+    let bad_address = HyperliquidClient::new_paper_trading(PaperConfig {
+        address: Some("0x123".to_string()),
+        ..PaperConfig::default()
+    });
+    checker.check_that("invalid address rejected", bad_address.is_err());
+    checker.check_that("bound to the zero address", client.get_address().as_deref() == Some("0x0000000000000000000000000000000000000000"));
+    client.paper_update_book("UBTC/USDC".to_string(), book(&[("99", "1"), ("98", "2")], &[("101", "0.5"), ("102", "1")]), 1_000);
+    let mids = client.get_all_mids();
+    checker.check("UBTC/USDC mid", mids.iter().find(|mid| mid.coin == "UBTC/USDC").map_or(0.0, |mid| mid.price.parse().unwrap_or(0.0)), 100.0);
+    checker.check("book levels", client.get_l2_orderbook("UBTC/USDC".to_string()).asks.len() as f64, 2.0);
+    checker.check_that("no paper feed on other clients", !HyperliquidClient::new().paper_update_mid("BTC".to_string(), 1.0, 0));
+
+    println!("\n🧪 [TEST 2] IOC buy of 1 UBTC sweeping two levels:");
+    let result = client.place_limit_order("UBTC/USDC".to_string(), true, "1".to_string(), "102".to_string(), "Ioc".to_string());
+    checker.check_that(&format!("filled: {}", result.message), result.success && result.filled_size.as_deref() == Some("1"));
+    checker.check("average price", result.avg_price.as_deref().unwrap_or("0").parse().unwrap_or(0.0), 101.5);
+    checker.check("USDC", balance(&client, "USDC").0, 10_000.0 - 101.5);
+    checker.check("UBTC after fee", balance(&client, "UBTC").0, 1.0 - TAKER);
+    checker.check("ask left at 102", client.get_l2_orderbook("UBTC/USDC".to_string()).asks[0].size.parse().unwrap_or(0.0), 0.5);
+
+    println!("\n🧪 [TEST 3] Resting sell, rejected orders:");
+    let resting = client.place_limit_order("UBTC/USDC".to_string(), false, "0.5".to_string(), "105".to_string(), "Gtc".to_string());
+    checker.check_that(&format!("resting: {}", resting.message), resting.success && resting.filled_size.is_none());
+    checker.check("UBTC on hold", balance(&client, "UBTC").1, 0.5);
+    checker.check("open orders", client.get_open_orders().unwrap_or_default().len() as f64, 1.0);
+    let post_only = client.place_limit_order("UBTC/USDC".to_string(), true, "0.1".to_string(), "200".to_string(), "Alo".to_string());
+    checker.check_that(&format!("crossing post-only rejected: {}", post_only.message), !post_only.success);
+    let too_big = client.place_limit_order("UBTC/USDC".to_string(), true, "1000".to_string(), "100".to_string(), "Gtc".to_string());
+    checker.check_that(&format!("oversized buy rejected: {}", too_big.message), !too_big.success);
+    let dust = client.place_limit_order("UBTC/USDC".to_string(), true, "0.0000000001".to_string(), "200".to_string(), "Gtc".to_string());
+    checker.check_that(&format!("dust size rejected: {}", dust.message), !dust.success);
+    let oversold = client.place_limit_order("UBTC/USDC".to_string(), false, "0.6".to_string(), "110".to_string(), "Gtc".to_string());
+    checker.check_that(&format!("selling held UBTC rejected: {}", oversold.message), !oversold.success);
+
+    println!("\n🧪 [TEST 4] Market moves through the resting sell:");
+    client.paper_update_book("UBTC/USDC".to_string(), book(&[("106", "0.3")], &[("107", "1")]), 2_000);
+    let orders = client.get_open_orders().unwrap_or_default();
+    checker.check("left on the book", orders[0].size.parse().unwrap_or(0.0), 0.2);
+    checker.check("USDC", balance(&client, "USDC").0, 10_000.0 - 101.5 + 0.3 * 105.0 * (1.0 - MAKER));
+    let maker_fill = client.get_fills(0, None).unwrap_or_default().pop().unwrap();
+    checker.check_that("filled as maker at the limit", !maker_fill.crossed && maker_fill.px == "105" && maker_fill.time == 2_000);
+    let cancel = client.cancel_order("UBTC/USDC".to_string(), orders[0].order_id);
+    checker.check_that(&format!("cancelled: {}", cancel.message), cancel.success);
+    checker.check("UBTC on hold", balance(&client, "UBTC").1, 0.0);
+    checker.check_that("second cancel fails", !client.cancel_order("UBTC/USDC".to_string(), orders[0].order_id).success);
+
+    println!("\n🧪 [TEST 5] Perp long at 2x, marked up, flipped short:");
+    let usdc_before = balance(&client, "USDC").0;
+    client.paper_update_mid("ETH".to_string(), 2000.0, 3_000);
+    let long = client.place_limit_order("ETH".to_string(), true, "1".to_string(), "2000".to_string(), "Gtc".to_string());
+    checker.check_that(&format!("filled: {}", long.message), long.success && long.filled_size.as_deref() == Some("1"));
+    client.paper_update_mid("ETH".to_string(), 2100.0, 4_000);
+    let position = client.get_positions().unwrap_or_default().remove(0);
+    checker.check("size", position.size.parse().unwrap_or(0.0), 1.0);
+    checker.check("unrealized", position.unrealized_pnl.parse().unwrap_or(0.0), 100.0);
+    checker.check("margin", position.margin_used.parse().unwrap_or(0.0), 1050.0);
+    let oversized = client.place_limit_order("ETH".to_string(), true, "100".to_string(), "2100".to_string(), "Gtc".to_string());
+    checker.check_that(&format!("oversized long rejected: {}", oversized.message), !oversized.success);
+    let flip = client.place_limit_order("ETH".to_string(), false, "2".to_string(), "2000".to_string(), "Ioc".to_string());
+    checker.check_that(&format!("filled: {}", flip.message), flip.success && flip.avg_price.as_deref() == Some("2100"));
+    let position = client.get_positions().unwrap_or_default().remove(0);
+    checker.check("short size", position.size.parse().unwrap_or(0.0), -1.0);
+    checker.check("short entry", position.entry_price.as_deref().unwrap_or("0").parse().unwrap_or(0.0), 2100.0);
+    checker.check("USDC", balance(&client, "USDC").0, usdc_before - 2000.0 * TAKER + 100.0 - 2.0 * 2100.0 * TAKER);
+    let flip_fill = client.get_fills(4_000, None).unwrap_or_default().pop().unwrap();
+    checker.check_that("reported as a flip", flip_fill.dir == "Long > Short" && flip_fill.closed_pnl == "100" && flip_fill.start_position == "1");
+
+    println!("\n🧪 [TEST 6] USDC to BTC swap on the paper book:");
+    client.paper_update_book("UBTC/USDC".to_string(), book(&[("106", "1")], &[("107", "1")]), 5_000);
+    let swap = client.swap_usdc_to_btc("100".to_string());
+    checker.check_that(&format!("swapped: {}", swap.message), swap.success && swap.avg_price.as_deref() == Some("107"));
+    checker.check("fills so far", client.get_fills(0, None).unwrap_or_default().len() as f64, 6.0);
+    let vault = client.vault_deposit("0x0000000000000000000000000000000000000001".to_string(), "10".to_string());
+    checker.check_that(&format!("other actions refused: {}", vault.message), !vault.success);
+
+    println!("\n🧪 [TEST 7] BTC orders and prices without metadata:");
+    let buy = client.place_btc_buy_order("53".to_string(), "106".to_string());
+    checker.check_that(&format!("buy order placed offline: {}", buy.message), buy.success);
+    let empty = HyperliquidClient::new_paper_trading(PaperConfig {
+        feed: PaperFeed::Recorded,
+        ..PaperConfig::default()
+    })
+    .expect("Invalid address");
+    let swap = empty.swap_usdc_to_btc("100".to_string());
+    checker.check_that(&format!("swap without a book fails: {}", swap.message), !swap.success);
+    checker.check_that("no BTC price", empty.get_btc_price() == "0.0");
+    checker.check_that("empty quote", empty.quote_usdc_to_btc("100".to_string()).price.is_empty());
+    checker.check_that("invalid amount quotes nothing", client.quote_btc_to_usdc("abc".to_string()).price.is_empty());
+
+    // Needs the exchange for books and metadata; without a network every call must still return
+    println!("\n🧪 [TEST 8] Live feed:");
+    let live = HyperliquidClient::new_paper_trading(PaperConfig {
+        feed: PaperFeed::Live,
+        balances: HashMap::from([("USDC".to_string(), 1_000.0)]),
+        ..PaperConfig::default()
+    })
+    .expect("Invalid address");
+    let swap = live.swap_usdc_to_btc("20".to_string());
+    checker.check_that(&format!("swap returned: {}", swap.message), swap.success || swap.message.starts_with("Failed to get"));
+    let order = live.place_limit_order("UBTC/USDC".to_string(), true, "0.001".to_string(), "1000".to_string(), "Gtc".to_string());
+    checker.check_that(&format!("resting order returned: {}", order.message), order.success || order.message.starts_with("Failed to get"));
+    checker.check_that("balances still readable", balance(&live, "USDC").0 > 0.0);
+
+    checker.finish();
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,58 @@
+mod test_harness;
+
+use hyperliquid_sdk_swift::{compute_performance, HistoryPoint, PortfolioPeriod};
+use test_harness::Checker;
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+fn history(values: &[&str]) -> Vec<HistoryPoint> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| HistoryPoint {
+            time: i as u64 * DAY_MS,
+            value: value.to_string(),
+        })
+        .collect()
+}
+
+fn period(account_values: &[&str], pnls: &[&str]) -> PortfolioPeriod {
+    PortfolioPeriod {
+        period: "week".to_string(),
+        account_value_history: history(account_values),
+        pnl_history: history(pnls),
+        volume: "0.0".to_string(),
+    }
+}
+
+fn main() {
+    println!("📈 [DEBUG] Testing portfolio performance metrics");
+    println!("================================================");
+
+    let mut checker = Checker::new();
+
+    println!("\n🧪 [TEST 1] Daily values with a 20 USDC deposit on day 3:");
+    let metrics = compute_performance(period(&["100", "110", "99", "129", "132"], &["0", "10", "-1", "9", "12"]), 0.04);
+    checker.check("pnl", metrics.pnl, 12.0);
+    checker.check("total return", metrics.total_return, 1.09 * 132.0 / 129.0 - 1.0);
+    checker.check("max drawdown", metrics.max_drawdown, 0.1);
+    checker.check("drawdown peak", metrics.drawdown_peak_time.unwrap_or(0) as f64, DAY_MS as f64);
+    checker.check("drawdown trough", metrics.drawdown_trough_time.unwrap_or(0) as f64, 2.0 * DAY_MS as f64);
+    checker.check("volatility", metrics.volatility, 1.8085380540550409);
+    checker.check("sharpe", metrics.sharpe_ratio.unwrap_or(0.0), 6.247734027543802);
+    checker.check("returns", metrics.return_count as f64, 4.0);
+
+    println!("\n🧪 [TEST 2] Account funded after the first point:");
+    let metrics = compute_performance(period(&["0", "50", "55"], &["0", "0", "5"]), 0.0);
+    checker.check("returns", metrics.return_count as f64, 1.0);
+    checker.check("total return", metrics.total_return, 0.1);
+    checker.check("max drawdown", metrics.max_drawdown, 0.0);
+
+    println!("\n🧪 [TEST 3] Flat account:");
+    let metrics = compute_performance(period(&["10", "10", "10"], &["0", "0", "0"]), 0.0);
+    checker.check("volatility", metrics.volatility, 0.0);
+    checker.check_that("no Sharpe without volatility", metrics.sharpe_ratio.is_none());
+
+    checker.finish();
+    println!("\n✅ [DEBUG] All tests completed!");
+}
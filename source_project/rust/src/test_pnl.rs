@@ -0,0 +1,89 @@
+mod test_harness;
+
+use hyperliquid_sdk_swift::{compute_pnl, CoinPnl, CostBasisMethod, UserFill};
+use std::collections::HashMap;
+use test_harness::Checker;
+
+// (time, side, size, price, fee, fee token)
+fn fills(coin: &str, start_position: &str, trades: &[(u64, &str, &str, &str, &str, &str)]) -> Vec<UserFill> {
+    trades
+        .iter()
+        .enumerate()
+        .map(|(i, &(time, side, sz, px, fee, fee_token))| UserFill {
+            coin: coin.to_string(),
+            px: px.to_string(),
+            sz: sz.to_string(),
+            side: side.to_string(),
+            time,
+            start_position: if i == 0 { start_position.to_string() } else { "0".to_string() },
+            dir: String::new(),
+            closed_pnl: "0.0".to_string(),
+            hash: format!("0x{:x}", time),
+            oid: time,
+            crossed: true,
+            fee: Some(fee.to_string()),
+            tid: Some(time),
+            fee_token: Some(fee_token.to_string()),
+        })
+        .collect()
+}
+
+fn pnl(fills: Vec<UserFill>, price: Option<f64>, method: CostBasisMethod) -> CoinPnl {
+    let coin = fills[0].coin.clone();
+    let prices: HashMap<String, f64> = price.map(|price| (coin, price)).into_iter().collect();
+    compute_pnl(fills, prices, method).remove(0)
+}
+
+fn main() {
+    println!("📒 [DEBUG] Testing the PnL engine on synthetic fills");
+    println!("===================================================");
+
+    let mut checker = Checker::new();
+
+    println!("\n🧪 [TEST 1] Buy 1 @ 100, buy 1 @ 110, sell 1 @ 120, mid 130:");
+    let long = fills("BTC", "0", &[(1, "B", "1", "100", "0.1", "USDC"), (2, "B", "1", "110", "0.1", "USDC"), (3, "A", "1", "120", "0.1", "USDC")]);
+    let fifo = pnl(long.clone(), Some(130.0), CostBasisMethod::Fifo);
+    checker.check("FIFO realized", fifo.realized_pnl, 20.0);
+    checker.check("FIFO entry", fifo.average_entry_price, 110.0);
+    checker.check("FIFO unrealized", fifo.unrealized_pnl, 20.0);
+    checker.check("fees", fifo.fees_paid, 0.3);
+    let average = pnl(long, Some(130.0), CostBasisMethod::AverageCost);
+    checker.check("average cost realized", average.realized_pnl, 15.0);
+    checker.check("average cost entry", average.average_entry_price, 105.0);
+    checker.check("average cost unrealized", average.unrealized_pnl, 25.0);
+    checker.check("position", average.position_size, 1.0);
+
+    println!("\n🧪 [TEST 2] Buy 2 @ 100, sell 3 @ 90 flips short, mid 80:");
+    let flip = pnl(fills("ETH", "0", &[(1, "B", "2", "100", "0", "USDC"), (2, "A", "3", "90", "0", "USDC")]), Some(80.0), CostBasisMethod::Fifo);
+    checker.check("realized", flip.realized_pnl, -20.0);
+    checker.check("position", flip.position_size, -1.0);
+    checker.check("entry", flip.average_entry_price, 90.0);
+    checker.check("unrealized", flip.unrealized_pnl, 10.0);
+
+    println!("\n🧪 [TEST 3] Sell 1 @ 50, sell 1 @ 40, buy 1 @ 30 (fills out of order):");
+    let short = fills("SOL", "0", &[(3, "B", "1", "30", "0", "USDC"), (1, "A", "1", "50", "0", "USDC"), (2, "A", "1", "40", "0", "USDC")]);
+    let fifo = pnl(short.clone(), None, CostBasisMethod::Fifo);
+    checker.check("FIFO realized", fifo.realized_pnl, 20.0);
+    checker.check("FIFO position", fifo.position_size, -1.0);
+    checker.check("FIFO entry", fifo.average_entry_price, 40.0);
+    checker.check("no mid, no unrealized", fifo.unrealized_pnl, 0.0);
+    let average = pnl(short, None, CostBasisMethod::AverageCost);
+    checker.check("average cost realized", average.realized_pnl, 15.0);
+    checker.check("average cost entry", average.average_entry_price, 45.0);
+
+    println!("\n🧪 [TEST 4] Spot buy 10 @ 2 with a 0.01 PURR fee, sell 5 @ 3 with a 0.015 USDC fee:");
+    let spot = pnl(fills("PURR/USDC", "0", &[(1, "B", "10", "2", "0.01", "PURR"), (2, "A", "5", "3", "0.015", "USDC")]), Some(2.5), CostBasisMethod::Fifo);
+    checker.check("position", spot.position_size, 4.99);
+    checker.check("fees in USDC", spot.fees_paid, 0.035);
+    checker.check("realized", spot.realized_pnl, 5.0);
+    checker.check("unrealized", spot.unrealized_pnl, 4.99 * 0.5);
+
+    println!("\n🧪 [TEST 5] History starting with 2 already held, sell 1 @ 100:");
+    let partial = pnl(fills("BTC", "2", &[(1, "A", "1", "100", "0", "USDC")]), None, CostBasisMethod::Fifo);
+    checker.check("position", partial.position_size, 1.0);
+    checker.check("realized", partial.realized_pnl, 0.0);
+    checker.check_that("flagged as incomplete", partial.incomplete_history);
+
+    checker.finish();
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,23 @@
+use hyperliquid_sdk_swift::HyperliquidClient;
+
+fn main() {
+    println!("📈 [DEBUG] Fetching live portfolio performance metrics");
+    println!("======================================================");
+
+    // Defaults to the protocol vault, which has a long history
+    let address = std::env::args().nth(1).unwrap_or_else(|| "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303".to_string());
+    println!("\n🌐 Portfolio metrics of {}:", address);
+    let client = HyperliquidClient::new();
+    for metrics in client.get_portfolio_metrics(address, 0.04) {
+        println!(
+            "  📊 {:<12} return {:>8.2}%  max drawdown {:>6.2}%  volatility {:>7.2}%  sharpe {}",
+            metrics.period,
+            metrics.total_return * 100.0,
+            metrics.max_drawdown * 100.0,
+            metrics.volatility * 100.0,
+            metrics.sharpe_ratio.map_or("-".to_string(), |sharpe| format!("{:.2}", sharpe)),
+        );
+    }
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
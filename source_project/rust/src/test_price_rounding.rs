@@ -4,16 +4,29 @@ fn main() {
     println!("🔧 [DEBUG] Testing Price Rounding for Sell Limit Orders");
     println!("====================================================");
 
-    // Load private key
-    let private_key = match std::fs::read_to_string("private-key.key") {
-        Ok(key) => key.trim().to_string(),
+    // Load encrypted keystore, password from the environment
+    let keystore_json = match std::fs::read_to_string("private-key.json") {
+        Ok(json) => json,
         Err(_) => {
-            println!("❌ [ERROR] Could not read private-key.key");
+            println!("❌ [ERROR] Could not read private-key.json (run test_keystore to create it)");
             return;
         }
     };
+    let password = match std::env::var("HYPERLIQUID_KEYSTORE_PASSWORD") {
+        Ok(password) if !password.is_empty() => password,
+        _ => {
+            println!("❌ [ERROR] HYPERLIQUID_KEYSTORE_PASSWORD must be set to a non-empty password");
+            std::process::exit(1);
+        }
+    };
 
-    let wallet_client = HyperliquidClient::new_with_wallet(private_key);
+    let wallet_client = match HyperliquidClient::new_with_keystore(keystore_json, password) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("❌ [ERROR] Could not unlock keystore: {}", e);
+            return;
+        }
+    };
 
     // Test with properly rounded values based on market data
     // Market price was $118144.5, so tick size appears to be $0.5
@@ -0,0 +1,46 @@
+use hyperliquid_sdk_swift::HyperliquidClient;
+
+fn main() {
+    println!("👥 [DEBUG] Testing read-only sub-account queries");
+    println!("===============================================");
+
+    // Master address from the command line, or the test address used elsewhere
+    let master = std::env::args().nth(1).unwrap_or_else(|| "0xa07d3500373300E7f4e13c440c3A0Ae9Ad5BB7C7".to_string());
+    let client = HyperliquidClient::new();
+
+    // Actions need a wallet, so a read-only client must refuse them without sending anything
+    println!("\n🧪 [TEST 1] Sub-account actions from a client without a wallet:");
+    let created = client.create_sub_account("test".to_string());
+    println!("  {} create_sub_account: {}", if created.success { "❌" } else { "✅" }, created.message);
+    let transfer = client.transfer_sub_account_usdc("not-an-address".to_string(), "1".to_string(), true);
+    println!("  {} transfer_sub_account_usdc: {}", if transfer.success { "❌" } else { "✅" }, transfer.message);
+    let order = client.place_sub_account_limit_order(
+        "not-an-address".to_string(),
+        "BTC".to_string(),
+        true,
+        "0.001".to_string(),
+        "1000".to_string(),
+        "Gtc".to_string(),
+    );
+    println!("  {} place_sub_account_limit_order: {}", if order.success { "❌" } else { "✅" }, order.message);
+
+    println!("\n🧪 [TEST 2] Sub-accounts of {}:", master);
+    let sub_accounts = client.get_sub_accounts(master.clone());
+    if sub_accounts.is_empty() {
+        println!("  ℹ️ No sub-accounts");
+    }
+    for sub_account in &sub_accounts {
+        println!(
+            "  • {} {} account value ${}, withdrawable ${}, {} spot balances",
+            sub_account.name,
+            sub_account.address,
+            sub_account.account_value,
+            sub_account.withdrawable,
+            sub_account.balances.len()
+        );
+        let status = if sub_account.master.eq_ignore_ascii_case(&master) { "✅" } else { "❌" };
+        println!("    {} master is {}", status, sub_account.master);
+    }
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,133 @@
+mod test_harness;
+
+use hyperliquid_sdk_swift::{build_tax_report, tax_report_csv, tax_report_json, FundingPayment, LedgerEntry, TaxReportSection, UserFill};
+use test_harness::Checker;
+
+const MAR_1_2023: u64 = 1677628800000;
+const JAN_1_2024: u64 = 1704067200000;
+const FEB_1_2024: u64 = 1706745600000;
+const MAR_10_2024: u64 = 1710028800000;
+const MAR_11_2024: u64 = 1710115200000;
+const JUN_1_2024: u64 = 1717200000000;
+const JUL_1_2024: u64 = 1719792000000;
+const AUG_1_2024: u64 = 1722470400000;
+const JAN_5_2025: u64 = 1736035200000;
+
+// (coin, time, side, size, price, fee, fee token)
+fn fill(coin: &str, time: u64, side: &str, sz: &str, px: &str, fee: &str, fee_token: &str) -> UserFill {
+    UserFill {
+        coin: coin.to_string(),
+        px: px.to_string(),
+        sz: sz.to_string(),
+        side: side.to_string(),
+        time,
+        start_position: "0".to_string(),
+        dir: if side == "B" { "Buy" } else { "Sell" }.to_string(),
+        closed_pnl: "0.0".to_string(),
+        hash: format!("0x{:x}", time),
+        oid: time,
+        crossed: true,
+        fee: Some(fee.to_string()),
+        tid: Some(time),
+        fee_token: Some(fee_token.to_string()),
+    }
+}
+
+fn main() {
+    println!("🧾 [DEBUG] Testing tax reports on synthetic history");
+    println!("===================================================");
+
+    let mut checker = Checker::new();
+
+    let fills = vec![
+        fill("BTC", MAR_1_2023, "B", "1", "100", "0.1", "USDC"),
+        fill("BTC", FEB_1_2024, "B", "1", "200", "0.1", "USDC"),
+        fill("BTC", JUN_1_2024, "A", "1.5", "300", "0.1", "USDC"),
+        fill("ETH", JUL_1_2024, "A", "2", "50", "0", "USDC"),
+        fill("ETH", AUG_1_2024, "B", "2", "40", "0", "USDC"),
+        fill("PURR/USDC", MAR_10_2024, "B", "10", "2", "0.01", "PURR"),
+        fill("PURR/USDC", MAR_11_2024, "A", "9.99", "3", "0.03", "USDC"),
+        fill("BTC", JAN_5_2025, "A", "0.5", "400", "0.1", "USDC"),
+    ];
+    let funding = vec![
+        FundingPayment { time: JUL_1_2024, date: String::new(), coin: "ETH".to_string(), usdc: 1.5, position_size: -2.0, funding_rate: 0.0001, hash: String::new() },
+        FundingPayment { time: JAN_5_2025, date: String::new(), coin: "BTC".to_string(), usdc: -0.5, position_size: 0.5, funding_rate: 0.0001, hash: String::new() },
+    ];
+    let ledger = vec![LedgerEntry {
+        time: JAN_1_2024,
+        date: String::new(),
+        kind: "deposit".to_string(),
+        token: "USDC".to_string(),
+        amount: 1000.0,
+        usd_value: Some(1000.0),
+        fee: 0.0,
+        hash: String::new(),
+        details: r#"{"type":"deposit","usdc":"1000.0"}"#.to_string(),
+    }];
+    let report = build_tax_report("0xabc".to_string(), 2024, fills, funding, ledger);
+
+    println!("\n🧪 [TEST 1] Year bounds and filtering:");
+    checker.check("start", report.start_time as f64, JAN_1_2024 as f64);
+    checker.check("end", report.end_time as f64, 1735689599999.0);
+    checker.check("trades in 2024", report.trades.len() as f64, 6.0);
+    checker.check("funding in 2024", report.funding.len() as f64, 1.0);
+    checker.check("ledger in 2024", report.ledger.len() as f64, 1.0);
+    checker.check_that("history is complete", !report.incomplete_history);
+
+    println!("\n🧪 [TEST 2] BTC bought in 2023 and 2024, 1.5 sold in 2024 (FIFO):");
+    let btc: Vec<_> = report.lots.iter().filter(|lot| lot.coin == "BTC").collect();
+    checker.check("lots", btc.len() as f64, 2.0);
+    checker.check("first lot size", btc[0].size, 1.0);
+    checker.check("first lot gain", btc[0].gain, 200.0);
+    checker.check_that("first lot acquired 2023-03-01", btc[0].acquired_date == "2023-03-01T00:00:00Z");
+    checker.check_that("first lot is long term", btc[0].long_term);
+    checker.check("second lot size", btc[1].size, 0.5);
+    checker.check("second lot proceeds", btc[1].proceeds, 150.0);
+    checker.check("second lot cost", btc[1].cost_basis, 100.0);
+    checker.check("second lot held days", btc[1].holding_days as f64, 121.0);
+    checker.check_that("second lot is short term", !btc[1].long_term);
+
+    println!("\n🧪 [TEST 3] ETH sold 2 @ 50, bought back @ 40:");
+    let eth: Vec<_> = report.lots.iter().filter(|lot| lot.coin == "ETH").collect();
+    checker.check("lots", eth.len() as f64, 1.0);
+    checker.check_that("lot is short", eth[0].is_short);
+    checker.check("proceeds", eth[0].proceeds, 100.0);
+    checker.check("cost", eth[0].cost_basis, 80.0);
+
+    println!("\n🧪 [TEST 4] Spot buy with a 0.01 PURR fee, sold after:");
+    let purr = report.lots.iter().find(|lot| lot.coin == "PURR/USDC").unwrap();
+    checker.check("lot size", purr.size, 9.99);
+    checker.check("gain", purr.gain, 9.99);
+    let buy = report.trades.iter().find(|trade| trade.coin == "PURR/USDC").unwrap();
+    checker.check("fee in USD", buy.fee_usd.unwrap_or(0.0), 0.02);
+    checker.check_that("USDC pair priced in USD", purr.usd_priced);
+
+    println!("\n🧪 [TEST 5] Summary:");
+    checker.check("realized gain", report.summary.realized_gain, 200.0 + 50.0 + 20.0 + 9.99);
+    checker.check("long term gain", report.summary.long_term_gain, 200.0);
+    checker.check("fees", report.summary.trading_fees_usd, 0.1 + 0.1 + 0.02 + 0.03);
+    checker.check("funding", report.summary.funding_net, 1.5);
+
+    println!("\n🧪 [TEST 6] Exports:");
+    let trades_csv = tax_report_csv(report.clone(), TaxReportSection::Trades);
+    checker.check("trade csv lines", trades_csv.lines().count() as f64, 7.0);
+    checker.check_that("trade csv header", trades_csv.starts_with("time,date,coin,side,"));
+    let ledger_csv = tax_report_csv(report.clone(), TaxReportSection::Ledger);
+    checker.check_that("ledger details quoted", ledger_csv.contains(r#""{""type"":""deposit"",""usdc"":""1000.0""}""#));
+    let json: serde_json::Value = serde_json::from_str(&tax_report_json(report)).unwrap_or_default();
+    checker.check("json lots", json["lots"].as_array().map_or(0, |lots| lots.len()) as f64, 4.0);
+    checker.check("json year", json["year"].as_f64().unwrap_or(0.0), 2024.0);
+
+    println!("\n🧪 [TEST 7] Pair quoted in another token, without its USD price:");
+    let usdt_fills = vec![
+        fill("HYPE/USDT", FEB_1_2024, "B", "1", "10", "0", "USDT"),
+        fill("HYPE/USDT", MAR_10_2024, "A", "1", "12", "0", "USDT"),
+    ];
+    let usdt_report = build_tax_report("0x1".to_string(), 2024, usdt_fills, vec![], vec![]);
+    checker.check("lots", usdt_report.lots.len() as f64, 1.0);
+    checker.check_that("lot flagged as unpriced", usdt_report.lots.iter().all(|lot| !lot.usd_priced));
+    checker.check("gain in USDT", usdt_report.summary.realized_gain, 2.0);
+
+    checker.finish();
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,48 @@
+use hyperliquid_sdk_swift::{HyperliquidClient, Trade, TradeListener};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+struct PrintingListener {
+    received: AtomicUsize,
+}
+
+impl TradeListener for PrintingListener {
+    fn on_trades(&self, trades: Vec<Trade>) {
+        for trade in &trades {
+            println!("  • {:?} {} @ {} (tid {})", trade.side, trade.size, trade.price, trade.tid);
+        }
+        self.received.fetch_add(trades.len(), Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    println!("📜 [DEBUG] Testing recent trades and the trades stream");
+    println!("======================================================");
+
+    let client = HyperliquidClient::new();
+
+    println!("\n🧪 [TEST 1] Recent BTC trades:");
+    let trades = client.get_recent_trades("BTC".to_string());
+    println!("  ✅ {} trades", trades.len());
+    for trade in trades.iter().take(5) {
+        println!("  • {:?} {} @ {} by {:?}", trade.side, trade.size, trade.price, trade.aggressor);
+    }
+
+    println!("\n🧪 [TEST 2] Streaming BTC trades for 15 seconds:");
+    let listener = Arc::new(PrintingListener { received: AtomicUsize::new(0) });
+    if !client.subscribe_trades("BTC".to_string(), listener.clone()) {
+        println!("  ❌ Subscription failed");
+        return;
+    }
+    std::thread::sleep(Duration::from_secs(15));
+    println!("  ✅ {} trades streamed", listener.received.load(Ordering::SeqCst));
+
+    println!("\n🧪 [TEST 3] Taker volume over the last minute:");
+    let volume = client.get_trade_volume("BTC".to_string(), 1);
+    println!("  • {} trades: bought {:.4} (${:.0}), sold {:.4} (${:.0})",
+        volume.trade_count, volume.buy_volume, volume.buy_notional, volume.sell_volume, volume.sell_notional);
+
+    println!("  {} Unsubscribed", if client.unsubscribe_trades("BTC".to_string()) { "✅" } else { "❌" });
+    println!("\n✅ [DEBUG] All tests completed!");
+}
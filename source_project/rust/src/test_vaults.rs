@@ -0,0 +1,40 @@
+use hyperliquid_sdk_swift::HyperliquidClient;
+
+fn main() {
+    println!("🏦 [DEBUG] Testing read-only vault queries");
+    println!("=========================================");
+
+    // The protocol vault (HLP) always exists and has followers
+    let vault = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303";
+    let user = std::env::args().nth(1).unwrap_or_else(|| "0xa07d3500373300E7f4e13c440c3A0Ae9Ad5BB7C7".to_string());
+    let client = HyperliquidClient::new();
+
+    // Transfers need a wallet, so a read-only client must refuse them without sending anything
+    println!("\n🧪 [TEST 1] Vault transfers from a client without a wallet:");
+    let deposit = client.vault_deposit(vault.to_string(), "10".to_string());
+    println!("  {} vault_deposit: {}", if deposit.success { "❌" } else { "✅" }, deposit.message);
+    let withdraw = client.vault_withdraw(vault.to_string(), "-1".to_string());
+    println!("  {} vault_withdraw of a negative amount: {}", if withdraw.success { "❌" } else { "✅" }, withdraw.message);
+    let invalid = client.vault_deposit("not-an-address".to_string(), "10".to_string());
+    println!("  {} vault_deposit to an invalid address: {}", if invalid.success { "❌" } else { "✅" }, invalid.message);
+
+    println!("\n🧪 [TEST 2] Vault details of {}:", vault);
+    let details = client.get_vault_details(vault.to_string());
+    println!("  • {} led by {}", details.name, details.leader);
+    println!("  • APR {:.2}%, closed: {}, deposits allowed: {}", details.apr * 100.0, details.is_closed, details.allow_deposits);
+    println!("  • Max withdrawable ${:.2} of ${:.2} distributable", details.max_withdrawable, details.max_distributable);
+    println!("  • {} portfolio periods, {} followers", details.portfolio.len(), details.followers.len());
+    let status = if details.vault_address.eq_ignore_ascii_case(vault) { "✅" } else { "❌" };
+    println!("  {} vault address is {}", status, details.vault_address);
+
+    println!("\n🧪 [TEST 3] Vault equities of {}:", user);
+    let equities = client.get_user_vault_equities(user);
+    if equities.is_empty() {
+        println!("  ℹ️ No vault deposits");
+    }
+    for equity in equities {
+        println!("  • {}: ${} (locked: {}, until {:?})", equity.vault_address, equity.equity, equity.is_locked, equity.locked_until);
+    }
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
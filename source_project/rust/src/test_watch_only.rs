@@ -0,0 +1,64 @@
+use hyperliquid_sdk_swift::HyperliquidClient;
+
+fn main() {
+    println!("👀 [DEBUG] Testing watch-only client");
+    println!("====================================");
+
+    // Test address with known spot balances and fills
+    let address = "0xa07d3500373300E7f4e13c440c3A0Ae9Ad5BB7C7";
+    let client = match HyperliquidClient::new_watch_only(address.to_string()) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("❌ [ERROR] Could not watch {}: {}", address, e);
+            std::process::exit(1);
+        }
+    };
+    println!("✅ [DEBUG] Watching {:?} (watch-only: {})", client.get_address(), client.is_watch_only());
+
+    // Misuse must come back as errors instead of panicking
+    println!("\n🧪 [TEST] Errors instead of panics:");
+    match HyperliquidClient::new_watch_only("not-an-address".to_string()) {
+        Ok(_) => println!("  ❌ Invalid address was accepted"),
+        Err(e) => println!("  ✅ new_watch_only: {}", e),
+    }
+    match client.watch_address("0x123".to_string()) {
+        Ok(_) => println!("  ❌ Invalid address was watched"),
+        Err(e) => println!("  ✅ watch_address: {}", e),
+    }
+    match HyperliquidClient::new().get_balances() {
+        Ok(_) => println!("  ❌ Unbound client returned balances"),
+        Err(e) => println!("  ✅ get_balances on new(): {}", e),
+    }
+
+    println!("\n💰 Balances:");
+    for balance in client.get_balances().unwrap_or_default() {
+        println!("  • {}: {} (hold {})", balance.coin, balance.total, balance.hold);
+    }
+
+    println!("\n📊 Positions:");
+    for position in client.get_positions().unwrap_or_default() {
+        println!("  • {} size={} entry={:?} uPnL={}", position.coin, position.size, position.entry_price, position.unrealized_pnl);
+    }
+
+    println!("\n📋 Open orders:");
+    for order in client.get_open_orders().unwrap_or_default() {
+        println!("  • #{} {} {} {} @ {}", order.order_id, order.coin, order.side, order.size, order.limit_price);
+    }
+
+    let now = chrono::Utc::now().timestamp_millis() as u64;
+    let fills = client.get_fills(now - 30 * 24 * 60 * 60 * 1000, Some(now)).unwrap_or_default();
+    println!("\n📈 {} fills in the last 30 days", fills.len());
+
+    // Signing must fail with a clear message instead of panicking
+    println!("\n🧪 [TEST] Placing an order from a watch-only client:");
+    let result = client.place_limit_order("@142".to_string(), true, "0.0001".to_string(), "1000".to_string(), "Gtc".to_string());
+    println!("  success={}, message='{}'", result.success, result.message);
+
+    println!("\n🧪 [TEST] Watching a second address:");
+    let _ = client.watch_address("0x0000000000000000000000000000000000000001".to_string());
+    for account in client.get_watched_accounts() {
+        println!("  • {}: {} balances, {} positions, {} open orders", account.address, account.balances.len(), account.positions.len(), account.open_orders.len());
+    }
+
+    println!("\n✅ [DEBUG] All tests completed!");
+}
@@ -0,0 +1,152 @@
+use crate::number;
+use std::collections::{HashSet, VecDeque};
+
+// Trades are kept this long for rolling volume, whatever window is asked for
+const MAX_TAPE_MINUTES: u32 = 60;
+const MINUTE_MS: u64 = 60 * 1000;
+
+/// Side of the taker, whose order crossed the book.
+#[derive(uniffi::Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+impl TradeSide {
+    // The API reports "B" when the taker bought and "A" when it sold
+    fn from_wire(side: &str) -> Self {
+        if side == "B" {
+            Self::Buy
+        } else {
+            Self::Sell
+        }
+    }
+}
+
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct Trade {
+    pub coin: String,
+    pub price: f64,
+    pub size: f64,
+    pub side: TradeSide,
+    pub time: u64,
+    pub tid: u64,
+    pub hash: String,
+    // Taker's address; only the recent trades query reports it
+    pub aggressor: Option<String>,
+}
+
+/// Taker volume over a window, in coins and USDC notional.
+#[derive(uniffi::Record, Debug, Clone)]
+pub struct TradeVolume {
+    pub coin: String,
+    pub window_start: u64,
+    pub window_end: u64,
+    pub buy_volume: f64,
+    pub sell_volume: f64,
+    pub buy_notional: f64,
+    pub sell_notional: f64,
+    pub trade_count: u32,
+}
+
+/// Receives trades as they print, on a background thread.
+///
+/// Streamed trades have no `aggressor`: the websocket client drops the
+/// `users` field, so look the taker up with `get_recent_trades` if needed.
+#[uniffi::export(with_foreign)]
+pub trait TradeListener: Send + Sync {
+    fn on_trades(&self, trades: Vec<Trade>);
+}
+
+pub(crate) fn parse_trade(trade: &serde_json::Value) -> Trade {
+    let side = TradeSide::from_wire(trade["side"].as_str().unwrap_or(""));
+    // users is [buyer, seller]
+    let aggressor = match side {
+        TradeSide::Buy => trade["users"][0].as_str(),
+        TradeSide::Sell => trade["users"][1].as_str(),
+    };
+    Trade {
+        coin: trade["coin"].as_str().unwrap_or("").to_string(),
+        price: trade["px"].as_str().map(number).unwrap_or(0.0),
+        size: trade["sz"].as_str().map(number).unwrap_or(0.0),
+        side,
+        time: trade["time"].as_u64().unwrap_or(0),
+        tid: trade["tid"].as_u64().unwrap_or(0),
+        hash: trade["hash"].as_str().unwrap_or("").to_string(),
+        aggressor: aggressor.map(|address| address.to_string()),
+    }
+}
+
+pub(crate) fn from_ws_trade(trade: &hyperliquid_rust_sdk::Trade) -> Trade {
+    Trade {
+        coin: trade.coin.clone(),
+        price: number(&trade.px),
+        size: number(&trade.sz),
+        side: TradeSide::from_wire(&trade.side),
+        time: trade.time,
+        tid: trade.tid,
+        hash: trade.hash.clone(),
+        aggressor: None,
+    }
+}
+
+/// Trades of one coin over the last `MAX_TAPE_MINUTES`, oldest first.
+#[derive(Default)]
+pub(crate) struct TradeTape {
+    trades: VecDeque<Trade>,
+    tids: HashSet<u64>,
+}
+
+impl TradeTape {
+    /// Adds trades not seen yet, returning them.
+    pub(crate) fn add(&mut self, trades: Vec<Trade>) -> Vec<Trade> {
+        let mut added = Vec::new();
+        for trade in trades {
+            if !self.tids.insert(trade.tid) {
+                continue;
+            }
+            // Snapshots and stream can overlap and arrive out of order
+            let position = self.trades.iter().rposition(|known| known.time <= trade.time).map_or(0, |i| i + 1);
+            self.trades.insert(position, trade.clone());
+            added.push(trade);
+        }
+
+        if let Some(latest) = self.trades.back().map(|trade| trade.time) {
+            let cutoff = latest.saturating_sub(MAX_TAPE_MINUTES as u64 * MINUTE_MS);
+            while self.trades.front().is_some_and(|trade| trade.time < cutoff) {
+                if let Some(trade) = self.trades.pop_front() {
+                    self.tids.remove(&trade.tid);
+                }
+            }
+        }
+        added
+    }
+
+    pub(crate) fn volume(&self, coin: &str, minutes: u32, now: u64) -> TradeVolume {
+        let window_start = now.saturating_sub(minutes.min(MAX_TAPE_MINUTES) as u64 * MINUTE_MS);
+        let mut volume = TradeVolume {
+            coin: coin.to_string(),
+            window_start,
+            window_end: now,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            buy_notional: 0.0,
+            sell_notional: 0.0,
+            trade_count: 0,
+        };
+        for trade in self.trades.iter().filter(|trade| trade.time >= window_start && trade.time <= now) {
+            match trade.side {
+                TradeSide::Buy => {
+                    volume.buy_volume += trade.size;
+                    volume.buy_notional += trade.size * trade.price;
+                }
+                TradeSide::Sell => {
+                    volume.sell_volume += trade.size;
+                    volume.sell_notional += trade.size * trade.price;
+                }
+            }
+            volume.trade_count += 1;
+        }
+        volume
+    }
+}